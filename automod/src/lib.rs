@@ -1,70 +1,103 @@
 use std::{
+    collections::VecDeque,
     fmt::Write,
     sync::{LazyLock, RwLock},
     time::UNIX_EPOCH,
 };
 
-use serde::Deserialize;
-use twilight_http::{
-    Client,
-    request::{AuditLogReason, TryIntoRequest},
+use plugin_sdk::{
+    discord_bot::plugin::{
+        discord_types::Requests,
+        host_functions::{discord_request, log},
+        host_types::LogLevels,
+        plugin_types::{
+            RegistrationsRequest, RegistrationsRequestDiscordEvents, RegistrationsResult,
+            SupportedRegistrations,
+        },
+    },
+    embed,
+    error::{self, PluginError},
+    exports::discord_bot::plugin::plugin_functions::{DiscordEvents, Guest},
+    locale::{Catalogs, DEFAULT_LOCALE, Locales},
+    registrations, request,
+    router::Router,
 };
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use twilight_http::request::AuditLogReason;
 use twilight_model::{
     channel::{
         Channel,
-        message::{
-            Embed,
-            embed::{EmbedAuthor, EmbedFooter},
-        },
+        message::{Embed, embed::EmbedFooter},
+    },
+    gateway::payload::incoming::{MessageCreate, MessageUpdate},
+    id::{
+        Id,
+        marker::{GuildMarker, MessageMarker},
     },
-    gateway::payload::incoming::MessageCreate,
-    id::Id,
     util::Timestamp,
 };
 
-wit_bindgen::generate!({ path: "../wit" });
+/// [Discord JSON Error Code] for missing permissions.
+///
+/// [Discord JSON Error Code]: https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
+const MISSING_PERMISSIONS: u32 = 50013;
 
-use crate::{
-    discord_bot::plugin::{
-        discord_types::{Contents, Requests},
-        host_functions::discord_request,
-        plugin_types::{
-            RegistrationsRequest, RegistrationsRequestDiscordEvents, SupportedRegistrations,
-        },
-    },
-    exports::discord_bot::plugin::plugin_functions::{DiscordEvents, Guest},
-};
+/// The amount of actioned messages which are remembered.
+const ACTIONED_MESSAGES_LIMIT: usize = 1000;
 
 struct Plugin {
+    supported_registrations: RwLock<SupportedRegistrations>,
     settings: RwLock<PluginSettings>,
+    registrations: RwLock<Option<RegistrationsRequest>>,
+    /// The most recent messages actions were taken on, their updates are not validated again.
+    actioned_messages: RwLock<VecDeque<Id<MessageMarker>>>,
 }
 
-#[derive(Deserialize)]
+/// Automatically moderates messages and reports the actions taken.
+#[derive(Deserialize, JsonSchema)]
 struct PluginSettings {
+    /// The ID of the channel automod reports are sent to.
     automod_channel_id: u64,
+    /// Whether time outs from multiple validations add up.
     #[serde(default = "PluginSettings::stack_time_outs_default")]
     stack_time_outs: bool,
+    /// Users and roles which are never moderated.
     bypass: Option<PluginSettingsBypass>,
+    /// The validations messages are checked against.
     #[serde(default)]
     validations: PluginSettingsValidations,
+    /// The locale of the automod reports, e.g. `en-US` or `nl`.
+    #[serde(default = "PluginSettings::locale_default")]
+    locale: String,
+    /// Overrides of the localized strings, keyed by locale and then by string key.
+    #[serde(default)]
+    strings: Catalogs,
 }
 
 impl PluginSettings {
     fn stack_time_outs_default() -> bool {
         true
     }
+
+    fn locale_default() -> String {
+        String::from(DEFAULT_LOCALE)
+    }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 struct PluginSettingsBypass {
+    /// The IDs of the users which bypass automod.
     #[serde(default)]
     users: Vec<u64>,
+    /// The IDs of the roles which bypass automod.
     #[serde(default)]
     roles: Vec<u64>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema, Serialize)]
 struct PluginSettingsValidations {
+    /// Messages without content which contain at least the configured amount of attachments.
     attachment_spam: Option<PluginSettingsAttachmentSpam>,
 }
 
@@ -76,10 +109,12 @@ impl Default for PluginSettingsValidations {
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema, Serialize)]
 struct PluginSettingsAttachmentSpam {
+    /// The minimum amount of attachments.
     #[serde(default)]
     count: usize,
+    /// The actions taken when the validation fails.
     #[serde(default)]
     actions: Actions,
 }
@@ -99,12 +134,15 @@ impl PluginSettingsAttachmentSpam {
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema, Serialize)]
 struct Actions {
+    /// Whether a report is sent to the automod channel.
     #[serde(default = "Actions::report_default")]
     report: bool,
+    /// The action taken on the message.
     #[serde(default)]
     message: Option<ActionsMessage>,
+    /// The action taken on the author of the message.
     #[serde(default)]
     user: Option<ActionsUser>,
 }
@@ -133,19 +171,35 @@ impl Actions {
     }
 }
 
-#[derive(Clone, Copy, Default, Deserialize)]
+#[derive(Clone, Copy, Default, Deserialize, JsonSchema, Serialize)]
 enum ActionsMessage {
     #[default]
     Delete,
 }
 
-#[derive(Clone, Copy, Deserialize)]
+#[derive(Clone, Copy, Deserialize, JsonSchema, Serialize)]
 enum ActionsUser {
     Ban,
+    Kick,
+    /// Adds the role with the provided ID to the user.
+    #[serde(rename = "quarantine")]
+    Quarantine(u64),
+    /// Times out the user for the provided amount of seconds.
     #[serde(rename = "time_out")]
     TimeOut(u64),
 }
 
+impl ActionsUser {
+    fn severity(self) -> u8 {
+        match self {
+            Self::TimeOut(_) => 0,
+            Self::Quarantine(_) => 1,
+            Self::Kick => 2,
+            Self::Ban => 3,
+        }
+    }
+}
+
 impl Default for ActionsUser {
     fn default() -> Self {
         Self::TimeOut(60)
@@ -158,15 +212,62 @@ struct TakeAction {
     user: Option<ActionsUser>,
 }
 
+struct ActionResults {
+    taken: Vec<String>,
+    failed: Vec<String>,
+}
+
+impl ActionResults {
+    /// action and permission are the string keys of the action and the permission it needs.
+    fn push(
+        &mut self,
+        locale: &str,
+        result: Result<(), PluginError>,
+        taken: String,
+        action: &str,
+        permission: &str,
+    ) {
+        match result {
+            Ok(()) => self.taken.push(taken),
+            Err(err) => self.failed.push(LOCALES.format(
+                locale,
+                "action.failed",
+                &[
+                    ("action", &LOCALES.text(locale, action)),
+                    ("reason", &describe(locale, &err, permission)),
+                ],
+            )),
+        }
+    }
+}
+
+fn describe(locale: &str, err: &PluginError, permission: &str) -> String {
+    match err {
+        PluginError::Discord(err) if err.code == Some(MISSING_PERMISSIONS) => LOCALES.format(
+            locale,
+            "action.failed.missing-permission",
+            &[("permission", &LOCALES.text(locale, permission))],
+        ),
+        err => err.to_string(),
+    }
+}
+
 static CONTEXT: LazyLock<Plugin> = LazyLock::new(|| Plugin {
+    supported_registrations: RwLock::new(SupportedRegistrations::empty()),
     settings: RwLock::new(PluginSettings {
         automod_channel_id: 0,
         stack_time_outs: PluginSettings::stack_time_outs_default(),
         bypass: None,
         validations: PluginSettingsValidations::default(),
+        locale: PluginSettings::locale_default(),
+        strings: Catalogs::new(),
     }),
+    registrations: RwLock::new(None),
+    actioned_messages: RwLock::new(VecDeque::new()),
 });
 
+static LOCALES: LazyLock<Locales> = LazyLock::new(|| plugin_sdk::locales!("en-US", "nl"));
+
 impl Guest for Plugin {
     fn initialization(
         settings: Vec<u8>,
@@ -178,32 +279,118 @@ impl Guest for Plugin {
             ));
         }
 
-        let settings = match sonic_rs::from_slice::<PluginSettings>(&settings) {
-            Ok(settings) => settings,
-            Err(err) => {
-                return Err(format!(
-                    "The provided settings were of the incorrect structure: {err}"
+        *CONTEXT.supported_registrations.write().unwrap() = supported_registrations;
+
+        Self::update_settings(&settings)?;
+
+        let registrations = Self::registrations();
+
+        *CONTEXT.registrations.write().unwrap() = Some(registrations.clone());
+
+        Ok(registrations)
+    }
+
+    fn settings_update(settings: Vec<u8>) -> Result<RegistrationsRequest, String> {
+        Self::update_settings(&settings)?;
+
+        let registrations = Self::registrations();
+
+        let previous_registrations = CONTEXT
+            .registrations
+            .write()
+            .unwrap()
+            .replace(registrations.clone());
+
+        Ok(registrations::diff(previous_registrations, registrations))
+    }
+
+    fn settings_schema() -> Vec<u8> {
+        sonic_rs::to_vec(&schema_for!(PluginSettings)).unwrap()
+    }
+
+    fn registrations_processed(registrations_result: RegistrationsResult) -> Result<(), String> {
+        registrations::log_result(registrations_result);
+
+        Ok(())
+    }
+
+    fn shutdown() -> Result<(), String> {
+        Ok(())
+    }
+
+    fn discord_event(event: DiscordEvents) -> Result<(), String> {
+        let result = match event {
+            DiscordEvents::MessageCreate(message_create_bytes) => {
+                error::deserialize::<Box<MessageCreate>>(&message_create_bytes, "message")
+                    .and_then(|message_create| Self::validate_message(&message_create))
+            }
+            DiscordEvents::MessageUpdate(message_update_bytes) => {
+                error::deserialize::<Box<MessageUpdate>>(&message_update_bytes, "message update")
+                    .and_then(|message_update| {
+                        // Updates without an edit, like embed unfurls, keep the validated content
+                        if message_update.edited_timestamp.is_none()
+                            || CONTEXT
+                                .actioned_messages
+                                .read()
+                                .unwrap()
+                                .contains(&message_update.id)
+                        {
+                            return Ok(());
+                        }
+
+                        Self::validate_message(&MessageCreate(message_update.0))
+                    })
+            }
+            DiscordEvents::InteractionCreate(interaction_create) => {
+                return Router::new().handle(&interaction_create);
+            }
+            _ => {
+                return Err(String::from(
+                    "The plugin received an event it did not register.",
                 ));
             }
         };
 
-        let get_channel_response = match discord_request(&Requests::GetChannel(
-            settings.automod_channel_id,
-        )) {
-            Ok(get_channel_response) => get_channel_response,
+        if let Err(err) = result {
+            err.log();
+        }
+
+        Ok(())
+    }
+
+    fn scheduled_job(job: String, _payload: Option<Vec<u8>>) -> Result<(), String> {
+        Err(format!("Unknown scheduled job: {job}"))
+    }
+
+    fn dependency_function(function: String, _params: Vec<u8>) -> Result<Vec<u8>, String> {
+        Err(format!(
+            "The {function} dependency function does not exist."
+        ))
+    }
+}
+
+impl Plugin {
+    fn update_settings(settings: &[u8]) -> Result<(), String> {
+        let settings = match sonic_rs::from_slice::<PluginSettings>(settings) {
+            Ok(settings) => settings,
             Err(err) => {
                 return Err(format!(
-                    "An error occured while trying to get information of the automod channel: {err}"
+                    "The provided settings were of the incorrect structure: {err}"
                 ));
             }
         };
 
-        if let Err(err) = sonic_rs::from_slice::<Channel>(get_channel_response.as_ref().unwrap()) {
+        if let Err(err) = request::fetch::<Channel>(
+            &Requests::GetChannel(settings.automod_channel_id),
+            "automod channel",
+        ) {
             return Err(format!(
-                "An error occured while deserializing the get channel response from Discord: {err}",
+                "An error occured while trying to get information of the automod channel: {err}"
             ));
         }
 
+        LOCALES.set_overrides(settings.strings);
+
         let mut ctx_settings = CONTEXT.settings.write().unwrap();
 
         ctx_settings.automod_channel_id = settings.automod_channel_id;
@@ -214,53 +401,41 @@ impl Guest for Plugin {
 
         ctx_settings.validations = settings.validations;
 
-        Ok(RegistrationsRequest {
+        ctx_settings.locale = settings.locale;
+
+        Ok(())
+    }
+
+    fn registrations() -> RegistrationsRequest {
+        RegistrationsRequest {
             discord_events: Some(RegistrationsRequestDiscordEvents {
-                interaction_create: None,
                 message_create: true,
-                thread_create: false,
-                thread_delete: false,
-                thread_list_sync: false,
-                thread_member_update: false,
-                thread_members_update: false,
-                thread_update: false,
+                message_update: CONTEXT
+                    .supported_registrations
+                    .read()
+                    .unwrap()
+                    .contains(SupportedRegistrations::DISCORD_EVENT_MESSAGE_UPDATE),
+                ..registrations::discord_events()
             }),
             scheduled_jobs: None,
             dependency_functions: None,
-        })
-    }
-
-    fn shutdown() -> Result<(), String> {
-        Ok(())
-    }
-
-    fn discord_event(event: DiscordEvents) -> Result<(), String> {
-        match event {
-            DiscordEvents::MessageCreate(message_create_bytes) => {
-                match sonic_rs::from_slice::<Box<MessageCreate>>(&message_create_bytes) {
-                    Ok(message_create) => Self::validate_message(&message_create),
-                    Err(err) => Err(err.to_string()),
-                }
-            }
-            _ => unimplemented!(),
         }
     }
 
-    fn scheduled_job(_job: String) -> Result<(), String> {
-        unimplemented!();
-    }
-
-    fn dependency_function(_function: String, _params: Vec<u8>) -> Result<Vec<u8>, String> {
-        unimplemented!();
-    }
-}
+    fn validate_message(message_create: &MessageCreate) -> Result<(), PluginError> {
+        // Only guild messages can be moderated
+        let Some(guild_id) = message_create.guild_id else {
+            return Ok(());
+        };
 
-impl Plugin {
-    fn validate_message(message_create: &MessageCreate) -> Result<(), String> {
         if Self::bypass(message_create) {
             return Ok(());
         }
 
+        let locale = CONTEXT.settings.read().unwrap().locale.clone();
+
+        let stack_time_outs = CONTEXT.settings.read().unwrap().stack_time_outs;
+
         let mut take_action = TakeAction {
             report: None,
             message: None,
@@ -268,12 +443,30 @@ impl Plugin {
         };
 
         if let Some(attachment_spam) = &CONTEXT.settings.read().unwrap().validations.attachment_spam
-            && let Some(new_take_action) = Self::attachment_spam(attachment_spam, message_create)
+            && let Some(new_take_action) =
+                Self::attachment_spam(attachment_spam, &locale, message_create)
+        {
+            Self::update_take_action(&mut take_action, new_take_action, stack_time_outs);
+        }
+
+        if take_action.report.is_none()
+            && take_action.message.is_none()
+            && take_action.user.is_none()
+        {
+            return Ok(());
+        }
+
         {
-            Self::update_take_action(&mut take_action, new_take_action);
+            let mut actioned_messages = CONTEXT.actioned_messages.write().unwrap();
+
+            if actioned_messages.len() == ACTIONED_MESSAGES_LIMIT {
+                actioned_messages.pop_front();
+            }
+
+            actioned_messages.push_back(message_create.id);
         }
 
-        Self::take_action(&take_action, message_create)?;
+        Self::take_action(&take_action, &locale, guild_id, message_create)?;
 
         Ok(())
     }
@@ -284,7 +477,11 @@ impl Plugin {
                 return true;
             }
 
-            for member_role in &message_create.member.as_ref().unwrap().roles {
+            for member_role in message_create
+                .member
+                .iter()
+                .flat_map(|member| &member.roles)
+            {
                 if bypass.roles.contains(&member_role.get()) {
                     return true;
                 }
@@ -296,6 +493,7 @@ impl Plugin {
 
     fn attachment_spam(
         attachment_spam: &PluginSettingsAttachmentSpam,
+        locale: &str,
         message: &MessageCreate,
     ) -> Option<TakeAction> {
         if !message.content.is_empty() {
@@ -306,8 +504,10 @@ impl Plugin {
 
         if attachment_count >= attachment_spam.count {
             let report = if attachment_spam.actions.report {
-                Some(format!(
-                    "Attachment spam ({attachment_count}), without message content"
+                Some(LOCALES.format(
+                    locale,
+                    "reason.attachment-spam",
+                    &[("count", &attachment_count)],
                 ))
             } else {
                 None
@@ -323,7 +523,13 @@ impl Plugin {
         None
     }
 
-    fn update_take_action(take_action: &mut TakeAction, new_take_action: TakeAction) {
+    /// Merges the actions of another validation, time outs add up when stack_time_outs is set and
+    /// the longest one is kept otherwise.
+    fn update_take_action(
+        take_action: &mut TakeAction,
+        new_take_action: TakeAction,
+        stack_time_outs: bool,
+    ) {
         if let Some(new_report) = new_take_action.report {
             if let Some(report) = &mut take_action.report {
                 let _ = write!(report, "\n- {new_report}");
@@ -349,14 +555,18 @@ impl Plugin {
 
         if let Some(new_user_action) = new_take_action.user {
             if let Some(user_action) = take_action.user {
-                match user_action {
-                    ActionsUser::Ban => (),
-                    ActionsUser::TimeOut(period) => match new_user_action {
-                        ActionsUser::Ban => take_action.user = new_take_action.user,
-                        ActionsUser::TimeOut(new_period) => {
-                            take_action.user = Some(ActionsUser::TimeOut(period + new_period));
-                        }
-                    },
+                match (user_action, new_user_action) {
+                    (ActionsUser::TimeOut(period), ActionsUser::TimeOut(new_period)) => {
+                        take_action.user = Some(ActionsUser::TimeOut(if stack_time_outs {
+                            period + new_period
+                        } else {
+                            period.max(new_period)
+                        }));
+                    }
+                    _ if new_user_action.severity() > user_action.severity() => {
+                        take_action.user = new_take_action.user;
+                    }
+                    _ => (),
                 }
             } else {
                 take_action.user = new_take_action.user;
@@ -364,56 +574,123 @@ impl Plugin {
         }
     }
 
-    fn take_action(take_action: &TakeAction, message: &MessageCreate) -> Result<(), String> {
+    fn take_action(
+        take_action: &TakeAction,
+        locale: &str,
+        guild_id: Id<GuildMarker>,
+        message: &MessageCreate,
+    ) -> Result<(), PluginError> {
+        let mut action_results = ActionResults {
+            taken: vec![],
+            failed: vec![],
+        };
+
         if let Some(message_action) = take_action.message {
             match message_action {
-                ActionsMessage::Delete => Self::delete_message(message)?,
+                ActionsMessage::Delete => action_results.push(
+                    locale,
+                    Self::delete_message(message),
+                    LOCALES.text(locale, "action.message-deleted"),
+                    "action.delete-message",
+                    "permission.manage-messages",
+                ),
             }
         }
 
         if let Some(user_action) = take_action.user {
             match user_action {
-                ActionsUser::Ban => Self::ban_user(take_action.report.as_deref(), message)?,
-                ActionsUser::TimeOut(period) => Self::time_out_user(message, period)?,
+                ActionsUser::Ban => action_results.push(
+                    locale,
+                    Self::ban_user(take_action.report.as_deref(), guild_id, message),
+                    LOCALES.text(locale, "action.user-banned"),
+                    "action.ban-user",
+                    "permission.ban-members",
+                ),
+                ActionsUser::Kick => action_results.push(
+                    locale,
+                    Self::kick_user(guild_id, message),
+                    LOCALES.text(locale, "action.user-kicked"),
+                    "action.kick-user",
+                    "permission.kick-members",
+                ),
+                ActionsUser::Quarantine(role) => action_results.push(
+                    locale,
+                    Self::quarantine_user(guild_id, message, role),
+                    LOCALES.format(
+                        locale,
+                        "action.user-quarantined",
+                        &[("role", &format!("<@&{role}>"))],
+                    ),
+                    "action.quarantine-user",
+                    "permission.manage-roles",
+                ),
+                ActionsUser::TimeOut(period) => action_results.push(
+                    locale,
+                    Self::time_out_user(guild_id, message, period),
+                    LOCALES.format(locale, "action.user-timed-out", &[("period", &period)]),
+                    "action.time-out-user",
+                    "permission.moderate-members",
+                ),
             }
         }
 
-        if take_action.report.is_some() {
-            Self::report(take_action, message)?;
+        for failed_action in &action_results.failed {
+            log(LogLevels::Warn, failed_action);
+        }
+
+        if let Some(report) = &take_action.report {
+            Self::report(report, &action_results, locale, message)?;
         }
 
         Ok(())
     }
 
-    fn report(take_action: &TakeAction, message: &MessageCreate) -> Result<(), String> {
-        let mut embed = Self::base_embed(message);
-
-        embed.description = Some(format!(
-            "**Reasons:**\n{}\n\n**Actions Taken:**",
-            take_action.report.as_ref().unwrap()
-        ));
-
-        let embed_description = embed.description.as_mut().unwrap();
-
-        if let Some(message_action) = take_action.message {
-            match message_action {
-                ActionsMessage::Delete => embed_description.push_str("\n- Message deleted"),
+    fn report(
+        report: &str,
+        action_results: &ActionResults,
+        locale: &str,
+        message: &MessageCreate,
+    ) -> Result<(), PluginError> {
+        let mut embed = Self::base_embed(locale, message);
+
+        let mut embed_description = format!(
+            "{}\n{report}\n\n{}",
+            LOCALES.text(locale, "report.reasons"),
+            LOCALES.text(locale, "report.actions-taken")
+        );
+
+        if action_results.taken.is_empty() {
+            let _ = write!(
+                embed_description,
+                "\n{}",
+                LOCALES.text(locale, "report.none")
+            );
+        } else {
+            for action_taken in &action_results.taken {
+                let _ = write!(embed_description, "\n- {action_taken}");
             }
         }
 
-        if let Some(user_action) = take_action.user {
-            match user_action {
-                ActionsUser::Ban => embed_description.push_str("\n- User banned"),
-                ActionsUser::TimeOut(period) => {
-                    let _ = write!(embed_description, "\n- User timed out for {period} seconds",);
-                }
+        if !action_results.failed.is_empty() {
+            let _ = write!(
+                embed_description,
+                "\n\n{}",
+                LOCALES.text(locale, "report.failed-actions")
+            );
+
+            for failed_action in &action_results.failed {
+                let _ = write!(embed_description, "\n- {failed_action}");
             }
         }
 
-        embed_description.push_str("\n\n**Message:**\n");
+        let _ = write!(
+            embed_description,
+            "\n\n{}\n",
+            LOCALES.text(locale, "report.message")
+        );
 
         if message.content.is_empty() {
-            embed_description.push_str("No Content");
+            embed_description.push_str(&LOCALES.text(locale, "report.no-content"));
         } else {
             embed_description.push_str(&message.content);
         }
@@ -421,7 +698,11 @@ impl Plugin {
         embed_description.push('\n');
 
         if message.attachments.is_empty() {
-            embed_description.push_str("\nNo Attachments");
+            let _ = write!(
+                embed_description,
+                "\n{}",
+                LOCALES.text(locale, "report.no-attachments")
+            );
         } else {
             for attachment in &message.attachments {
                 embed_description.push('\n');
@@ -429,30 +710,22 @@ impl Plugin {
             }
         }
 
-        let client = Client::builder().build();
+        embed.description = Some(embed_description);
 
-        let create_message_request = match client
-            .create_message(Id::new(CONTEXT.settings.read().unwrap().automod_channel_id))
-            .embeds(&[embed])
-            .try_into_request()
-        {
-            Ok(create_message_request) => create_message_request,
-            Err(err) => {
-                return Err(format!(
-                    "An error occured while creating the report create message request: {err}"
-                ));
-            }
-        };
+        let automod_channel_id = CONTEXT.settings.read().unwrap().automod_channel_id;
 
-        discord_request(&Requests::CreateMessage((
-            CONTEXT.settings.read().unwrap().automod_channel_id,
-            Contents::Json(create_message_request.body().unwrap().to_owned()),
-        )))?;
+        let contents = request::contents(
+            request::client()
+                .create_message(Id::new(automod_channel_id))
+                .embeds(&[embed]),
+        )?;
+
+        discord_request(&Requests::CreateMessage((automod_channel_id, contents)))?;
 
         Ok(())
     }
 
-    fn delete_message(message: &MessageCreate) -> Result<(), String> {
+    fn delete_message(message: &MessageCreate) -> Result<(), PluginError> {
         discord_request(&Requests::DeleteMessage((
             message.channel_id.get(),
             message.id.get(),
@@ -461,105 +734,89 @@ impl Plugin {
         Ok(())
     }
 
-    fn time_out_user(message: &MessageCreate, period: u64) -> Result<(), String> {
-        let client = Client::builder().build();
-
-        let update_member_request = match client
-            .update_guild_member(message.guild_id.unwrap(), message.author.id)
-            .communication_disabled_until(Some(
-                Timestamp::from_secs(
-                    (UNIX_EPOCH.elapsed().unwrap_or_default().as_secs() + period)
-                        .try_into()
-                        .unwrap_or_default(),
-                )
-                .unwrap_or(Timestamp::from_secs(0).unwrap()),
-            ))
-            .try_into_request()
-        {
-            Ok(update_member_request) => update_member_request,
-            Err(err) => {
-                return Err(format!(
-                    "An error occured while creating the update member request: {err}"
-                ));
-            }
-        };
+    fn time_out_user(
+        guild_id: Id<GuildMarker>,
+        message: &MessageCreate,
+        period: u64,
+    ) -> Result<(), PluginError> {
+        let body = request::body(
+            request::client()
+                .update_guild_member(guild_id, message.author.id)
+                .communication_disabled_until(Some(
+                    Timestamp::from_secs(
+                        (UNIX_EPOCH.elapsed().unwrap_or_default().as_secs() + period)
+                            .try_into()
+                            .unwrap_or_default(),
+                    )
+                    .unwrap_or(Timestamp::from_secs(0).unwrap()),
+                )),
+        )?;
 
         discord_request(&Requests::UpdateMember((
-            message.guild_id.unwrap().get(),
+            guild_id.get(),
             message.author.id.get(),
-            update_member_request.body().unwrap().to_owned(),
+            body,
         )))?;
 
         Ok(())
     }
 
-    fn ban_user(reason: Option<&str>, message: &MessageCreate) -> Result<(), String> {
-        let client = Client::builder().build();
-
-        let create_ban_request = match client
-            .create_ban(message.guild_id.unwrap(), message.author.id)
-            .reason(reason.unwrap_or("No reason provided"))
-            .try_into_request()
-        {
-            Ok(create_ban_request) => create_ban_request,
-            Err(err) => {
-                return Err(format!(
-                    "An error occured while creating the create ban request: {err}"
-                ));
-            }
-        };
+    fn ban_user(
+        reason: Option<&str>,
+        guild_id: Id<GuildMarker>,
+        message: &MessageCreate,
+    ) -> Result<(), PluginError> {
+        let body = request::body(
+            request::client()
+                .create_ban(guild_id, message.author.id)
+                .reason(reason.unwrap_or("No reason provided")),
+        )?;
 
         discord_request(&Requests::CreateBan((
-            message.guild_id.unwrap().get(),
+            guild_id.get(),
             message.author.id.get(),
-            create_ban_request.body().unwrap().to_owned(),
+            body,
         )))?;
 
         Ok(())
     }
 
-    fn base_embed(message: &MessageCreate) -> Embed {
-        Embed {
-            author: Some(EmbedAuthor {
-                icon_url: message.author.avatar.map(|avatar| {
-                    format!(
-                        "https://cdn.discordapp.com/avatars/{}/{}.webp",
-                        message.author.id.get(),
-                        avatar
-                    )
-                }),
-                name: message.author.name.clone(),
-                proxy_icon_url: None,
-                url: None,
-            }),
-            color: Some(0x00E7_2323),
-            description: None,
-            fields: vec![],
-            footer: Some(EmbedFooter {
-                icon_url: None,
-                proxy_icon_url: None,
-                text: format!("ID: {}", message.author.id.get()),
-            }),
-            image: None,
-            kind: String::from("rich"),
-            provider: None,
-            thumbnail: None,
-            timestamp: Some(
-                Timestamp::from_secs(
-                    UNIX_EPOCH
-                        .elapsed()
-                        .unwrap_or_default()
-                        .as_secs()
-                        .try_into()
-                        .unwrap_or_default(),
-                )
-                .unwrap_or(Timestamp::from_secs(0).unwrap()),
-            ),
-            title: Some(String::from("Automod Report")),
-            url: None,
-            video: None,
-        }
+    fn kick_user(guild_id: Id<GuildMarker>, message: &MessageCreate) -> Result<(), PluginError> {
+        discord_request(&Requests::RemoveGuildMember((
+            guild_id.get(),
+            message.author.id.get(),
+        )))?;
+
+        Ok(())
+    }
+
+    fn quarantine_user(
+        guild_id: Id<GuildMarker>,
+        message: &MessageCreate,
+        role: u64,
+    ) -> Result<(), PluginError> {
+        discord_request(&Requests::AddGuildMemberRole((
+            guild_id.get(),
+            message.author.id.get(),
+            role,
+        )))?;
+
+        Ok(())
+    }
+
+    fn base_embed(locale: &str, message: &MessageCreate) -> Embed {
+        let mut embed = embed::base();
+
+        embed.author = Some(embed::author(&message.author));
+        embed.footer = Some(EmbedFooter {
+            icon_url: None,
+            proxy_icon_url: None,
+            text: LOCALES.format(locale, "report.footer", &[("id", &message.author.id)]),
+        });
+        embed.title = Some(LOCALES.text(locale, "report.title"));
+
+        embed
     }
 }
 
-export!(Plugin);
+plugin_sdk::export!(Plugin);
@@ -0,0 +1,165 @@
+//! The `request_cat` dependency function, for plugins which want to embed cats.
+//!
+//! The params are a JSON [`RequestCatParams`] object, every field is optional:
+//!
+//! ```json
+//! {"id": null, "breed": "beng", "mime_types": ["jpg", "png"]}
+//! ```
+//!
+//! A successful result is a JSON [`RequestCatResult`] object, `width`, `height`, `mime_type` and
+//! `breed` are null when unknown:
+//!
+//! ```json
+//! {
+//!     "id": "O3btzLlsO",
+//!     "url": "https://cdn2.thecatapi.com/images/O3btzLlsO.png",
+//!     "width": 1100,
+//!     "height": 1100,
+//!     "mime_type": "image/png",
+//!     "breed": {"id": "beng", "name": "Bengal"}
+//! }
+//! ```
+//!
+//! A failed result is the string of a JSON [`RequestCatError`] object, `kind` is
+//! `invalid-params`, `not-found` or `request`:
+//!
+//! ```json
+//! {"kind": "invalid-params", "message": "The beng! cat request filter was not valid."}
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CONTEXT,
+    http::{self, CatQuery, CatResponse},
+};
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct RequestCatParams {
+    /// The ID of a specific cat, the other filters are ignored when set.
+    id: Option<String>,
+    /// The ID of the breed, e.g. `beng`.
+    breed: Option<String>,
+    /// The allowed image types: `jpg`, `png` and `gif`. All types are allowed when empty.
+    mime_types: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct RequestCatResult {
+    id: String,
+    url: String,
+    width: Option<u16>,
+    height: Option<u16>,
+    mime_type: Option<String>,
+    breed: Option<RequestCatResultBreed>,
+}
+
+#[derive(Serialize)]
+pub struct RequestCatResultBreed {
+    id: String,
+    name: String,
+}
+
+#[derive(Serialize)]
+pub struct RequestCatError {
+    kind: RequestCatErrorKind,
+    message: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RequestCatErrorKind {
+    /// The params were not a valid JSON object or contained an invalid filter.
+    InvalidParams,
+    /// No cat matched the filters.
+    NotFound,
+    /// The image provider request failed or does not support the filters.
+    Request,
+}
+
+impl From<CatResponse> for RequestCatResult {
+    fn from(cat: CatResponse) -> Self {
+        Self {
+            mime_type: cat
+                .mime_type
+                .or_else(|| mime_type(&cat.url).map(String::from)),
+            breed: cat
+                .breeds
+                .into_iter()
+                .next()
+                .map(|breed| RequestCatResultBreed {
+                    id: breed.id,
+                    name: breed.name,
+                }),
+            id: cat.id,
+            url: cat.url,
+            width: cat.width,
+            height: cat.height,
+        }
+    }
+}
+
+pub fn request_cat(params: &[u8]) -> Result<Vec<u8>, String> {
+    let params = match sonic_rs::from_slice::<RequestCatParams>(params) {
+        Ok(params) => params,
+        Err(err) => {
+            return Err(error(
+                RequestCatErrorKind::InvalidParams,
+                format!("The provided params were of the incorrect structure, error: {err}"),
+            ));
+        }
+    };
+
+    if let Some(parameter) = params
+        .id
+        .iter()
+        .chain(&params.breed)
+        .chain(&params.mime_types)
+        .find(|parameter| !http::valid_parameter(parameter))
+    {
+        return Err(error(
+            RequestCatErrorKind::InvalidParams,
+            format!("The {parameter} cat request filter was not valid."),
+        ));
+    }
+
+    let cat_response = CONTEXT.http_client.request_cat(&CatQuery {
+        id: params.id,
+        breed: params.breed,
+        mime_types: params.mime_types,
+        ..Default::default()
+    });
+
+    let cat = match cat_response {
+        Ok(mut cat_response) => match cat_response.pop() {
+            Some(cat) => cat,
+            None => {
+                return Err(error(
+                    RequestCatErrorKind::NotFound,
+                    String::from("No cat matched the provided filters."),
+                ));
+            }
+        },
+        Err(err) => return Err(error(RequestCatErrorKind::Request, err)),
+    };
+
+    CONTEXT.update_stats(|stats| stats.total_cats_requested += 1);
+
+    Ok(sonic_rs::to_vec(&RequestCatResult::from(cat)).unwrap())
+}
+
+/// The failed result, a JSON [`RequestCatError`] object.
+fn error(kind: RequestCatErrorKind, message: String) -> String {
+    sonic_rs::to_string(&RequestCatError { kind, message }).unwrap()
+}
+
+/// The MIME type of the image based on the file extension of its URL.
+fn mime_type(url: &str) -> Option<&'static str> {
+    match url.rsplit_once('.')?.1 {
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "gif" => Some("image/gif"),
+        _ => None,
+    }
+}
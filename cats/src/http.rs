@@ -1,16 +1,57 @@
-use std::env;
-
-use serde::Deserialize;
+use std::sync::RwLock;
 
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeOwned};
+use sonic_rs::{JsonValueTrait, Value};
 use wstd::{
-    http::{Client, Request},
+    http::{Client, Method, Request},
+    rand::get_insecure_random_bytes,
     runtime::block_on,
 };
 
-use crate::CONTEXT;
-
 pub struct HttpClient {
     client: Client,
+    provider: RwLock<Provider>,
+}
+
+/// Where the images come from.
+#[derive(PartialEq)]
+pub enum Provider {
+    /// TheCatAPI or an API with the same endpoints, like TheDogAPI. The only provider which
+    /// supports filters, breeds and favourites.
+    CatApi {
+        /// Ends with a slash, e.g. `https://api.thecatapi.com/v1/`.
+        base_url: String,
+        api_key: Option<String>,
+    },
+    /// Any endpoint returning JSON, the image URL and ID are taken from it with JSON pointers.
+    Json {
+        url: String,
+        headers: Vec<(String, String)>,
+        url_pointer: String,
+        /// The file name of the image URL is used as ID when missing.
+        id_pointer: Option<String>,
+    },
+    /// A random URL of the list.
+    Static { urls: Vec<String> },
+}
+
+/// The filters of a cat request, an ID takes precedence over the other filters.
+#[derive(Default)]
+pub struct CatQuery {
+    pub id: Option<String>,
+    /// The ID of the breed, e.g. `beng`.
+    pub breed: Option<String>,
+    /// The allowed image types: `jpg`, `png` and `gif`. All types are allowed when empty.
+    pub mime_types: Vec<String>,
+    /// The amount of cats, at most 100 and ignored for IDs. Only TheCatAPI compatible providers
+    /// return more than one cat, 0 is handled as 1.
+    pub limit: u8,
+}
+
+impl CatQuery {
+    pub fn filtered(&self) -> bool {
+        self.id.is_some() || self.breed.is_some() || !self.mime_types.is_empty()
+    }
 }
 
 // API response types
@@ -21,86 +62,277 @@ pub struct CatResponse {
     pub id: String,
     pub url: String,
     pub width: Option<u16>,
-    pub heigth: Option<u16>,
+    pub height: Option<u16>,
     pub mime_type: Option<String>,
-    //breeds: Vec<CatResponseBreed>,
+    #[serde(default)]
+    pub breeds: Vec<CatResponseBreed>,
     //favourite: Option<CatResponseFavourite>,
 }
 
-//#[derive(Deserialize)]
-//#[allow(dead_code)]
-//struct CatResponseBreed {
-//    weight: CatResponseBreedWeight,
-//    id: String,
-//    name: String,
-//    cfa_url: String,
-//    vetstreet_url: String,
-//    vcahospitals_url: String,
-//    temperament: String,
-//    origin: String,
-//    country_codes: String,
-//    country_code: String,
-//    description: String,
-//    life_span: String,
-//    indoor: u8,
-//    lap: u8,
-//    alt_names: String,
-//    adaptability: u8,
-//    affection_level: u8,
-//    child_friendly: u8,
-//    dog_friendly: u8,
-//    energy_level: u8,
-//    grooming: u8,
-//    health_issues: u8,
-//    intelligence: u8,
-//    shedding_level: u8,
-//    social_needs: u8,
-//    stranger_friendly: u8,
-//    vocalisation: u8,
-//    experimental: u8,
-//    hairless: u8,
-//    natural: u8,
-//    rare: u8,
-//    rex: u8,
-//    suppressed_tail: u8,
-//    short_legs: u8,
-//    wikipedia_url: String,
-//    hypoallergenic: u8,
-//    reference_image_id: String,
-//}
-
-//#[derive(Deserialize)]
-//#[allow(dead_code)]
-//struct CatResponseBreedWeight {
-//    imperial: String,
-//    metric: String,
-//}
-//
-//#[derive(Deserialize)]
-//#[allow(dead_code)]
-//struct CatResponseFavourite {}
+/// Most attributes are missing for some breeds.
+#[derive(Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct CatResponseBreed {
+    pub weight: Option<CatResponseBreedWeight>,
+    #[serde(deserialize_with = "string_or_number")]
+    pub id: String,
+    pub name: String,
+    pub cfa_url: Option<String>,
+    pub vetstreet_url: Option<String>,
+    pub vcahospitals_url: Option<String>,
+    pub temperament: Option<String>,
+    pub origin: Option<String>,
+    pub country_codes: Option<String>,
+    pub country_code: Option<String>,
+    pub description: Option<String>,
+    pub life_span: Option<String>,
+    pub indoor: Option<u8>,
+    pub lap: Option<u8>,
+    pub alt_names: Option<String>,
+    pub adaptability: Option<u8>,
+    pub affection_level: Option<u8>,
+    pub child_friendly: Option<u8>,
+    pub dog_friendly: Option<u8>,
+    pub energy_level: Option<u8>,
+    pub grooming: Option<u8>,
+    pub health_issues: Option<u8>,
+    pub intelligence: Option<u8>,
+    pub shedding_level: Option<u8>,
+    pub social_needs: Option<u8>,
+    pub stranger_friendly: Option<u8>,
+    pub vocalisation: Option<u8>,
+    pub experimental: Option<u8>,
+    pub hairless: Option<u8>,
+    pub natural: Option<u8>,
+    pub rare: Option<u8>,
+    pub rex: Option<u8>,
+    pub suppressed_tail: Option<u8>,
+    pub short_legs: Option<u8>,
+    pub wikipedia_url: Option<String>,
+    pub hypoallergenic: Option<u8>,
+    pub reference_image_id: Option<String>,
+    /// Only set on the breeds list.
+    pub image: Option<CatResponseBreedImage>,
+}
+
+#[derive(Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct CatResponseBreedWeight {
+    pub imperial: String,
+    pub metric: String,
+}
+
+#[derive(Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct CatResponseBreedImage {
+    pub id: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+pub struct FavouriteResponse {
+    pub id: u64,
+    pub image_id: String,
+    pub sub_id: Option<String>,
+    pub image: Option<FavouriteResponseImage>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+pub struct FavouriteResponseImage {
+    pub id: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FavouriteRequest<'a> {
+    image_id: &'a str,
+    sub_id: &'a str,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct FavouriteCreatedResponse {
+    id: u64,
+}
 
 impl HttpClient {
     pub fn new() -> Self {
         HttpClient {
             client: Client::new(),
+            provider: RwLock::new(Provider::CatApi {
+                base_url: String::from("https://api.thecatapi.com/v1/"),
+                api_key: None,
+            }),
         }
     }
 
-    pub fn request_cat(&self, id: Option<String>) -> Result<Vec<CatResponse>, String> {
-        CONTEXT.stats.write().unwrap().total_cats_requested += 1;
+    /// Returns whether the provider changed.
+    pub fn set_provider(&self, provider: Provider) -> bool {
+        let mut current_provider = self.provider.write().unwrap();
 
-        let mut uri = String::from("https://api.thecatapi.com/v1/images/");
+        if *current_provider == provider {
+            return false;
+        }
 
-        match id {
-            Some(id) => uri.push_str(&id),
-            None => uri.push_str("search"),
+        *current_provider = provider;
+
+        true
+    }
+
+    /// Whether the provider has the TheCatAPI endpoints, see [`Provider::CatApi`].
+    pub fn supports_cat_api(&self) -> bool {
+        matches!(*self.provider.read().unwrap(), Provider::CatApi { .. })
+    }
+
+    pub fn request_cat(&self, query: &CatQuery) -> Result<Vec<CatResponse>, String> {
+        for parameter in query.id.iter().chain(&query.breed).chain(&query.mime_types) {
+            if !valid_parameter(parameter) {
+                return Err(format!("The {parameter} cat request filter was not valid."));
+            }
         }
 
-        let request = match Request::get(&uri)
-            .header("x-api-key", env::var("API_KEY").unwrap())
-            .body(())
-        {
+        match &*self.provider.read().unwrap() {
+            Provider::CatApi { .. } => {}
+            _ if query.filtered() => {
+                return Err(String::from(
+                    "The configured image provider does not support cat request filters.",
+                ));
+            }
+            Provider::Json {
+                url,
+                headers,
+                url_pointer,
+                id_pointer,
+            } => {
+                let headers = headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))
+                    .collect::<Vec<_>>();
+
+                let json = self.send::<Value>(Method::GET, url, &headers, vec![])?;
+
+                let Some(url) = pointer(&json, url_pointer).and_then(|url| url.as_str()) else {
+                    return Err(format!(
+                        "The JSON response did not contain an image URL at {url_pointer}."
+                    ));
+                };
+
+                let id = id_pointer
+                    .as_deref()
+                    .and_then(|id_pointer| pointer(&json, id_pointer))
+                    .and_then(|id| match id.as_str() {
+                        Some(id) => Some(id.to_string()),
+                        None => id.as_u64().map(|id| id.to_string()),
+                    });
+
+                return Ok(vec![CatResponse::from_url(id, url.to_string())]);
+            }
+            Provider::Static { urls } => {
+                let mut bytes = [0; 4];
+
+                get_insecure_random_bytes(&mut bytes);
+
+                let Some(url) = urls.get(u32::from_le_bytes(bytes) as usize % urls.len().max(1))
+                else {
+                    return Err(String::from("The static image provider has no URLs."));
+                };
+
+                return Ok(vec![CatResponse::from_url(None, url.clone())]);
+            }
+        }
+
+        let mut uri = String::from("images/");
+
+        match &query.id {
+            Some(id) => uri.push_str(id),
+            None => {
+                uri.push_str(&format!("search?limit={}", query.limit.clamp(1, 100)));
+
+                if let Some(breed) = &query.breed {
+                    uri.push_str("&breed_ids=");
+                    uri.push_str(breed);
+                }
+
+                if !query.mime_types.is_empty() {
+                    uri.push_str("&mime_types=");
+                    uri.push_str(&query.mime_types.join(","));
+                }
+            }
+        }
+
+        // Images requested by ID are not wrapped in a list
+        if query.id.is_some() {
+            self.api::<CatResponse>(Method::GET, &uri, vec![])
+                .map(|cat_response| vec![cat_response])
+        } else {
+            self.api::<Vec<CatResponse>>(Method::GET, &uri, vec![])
+        }
+    }
+
+    pub fn request_breeds(&self) -> Result<Vec<CatResponseBreed>, String> {
+        self.api(Method::GET, "breeds", vec![])
+    }
+
+    /// The favourites of the sub ID, the newest first. TheCatAPI returns at most 100.
+    pub fn request_favourites(&self, sub_id: &str) -> Result<Vec<FavouriteResponse>, String> {
+        if !valid_parameter(sub_id) {
+            return Err(format!("The {sub_id} favourites sub ID was not valid."));
+        }
+
+        self.api(
+            Method::GET,
+            &format!("favourites?sub_id={sub_id}&limit=100&order=DESC"),
+            vec![],
+        )
+    }
+
+    /// Favourites the image under the sub ID, TheCatAPI rejects duplicate favourites.
+    pub fn favourite(&self, image_id: &str, sub_id: &str) -> Result<(), String> {
+        let body = sonic_rs::to_vec(&FavouriteRequest { image_id, sub_id }).unwrap();
+
+        self.api::<FavouriteCreatedResponse>(Method::POST, "favourites", body)?;
+
+        Ok(())
+    }
+
+    /// Sends a request to the TheCatAPI endpoint, path is relative to the base URL.
+    fn api<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Vec<u8>,
+    ) -> Result<T, String> {
+        let Provider::CatApi { base_url, api_key } = &*self.provider.read().unwrap() else {
+            return Err(String::from(
+                "The configured image provider does not support breeds and favourites.",
+            ));
+        };
+
+        let mut headers = vec![("content-type", "application/json")];
+
+        if let Some(api_key) = api_key {
+            headers.push(("x-api-key", api_key));
+        }
+
+        self.send(method, &format!("{base_url}{path}"), &headers, body)
+    }
+
+    fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        uri: &str,
+        headers: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> Result<T, String> {
+        let mut builder = Request::builder().method(method).uri(uri);
+
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+
+        let request = match builder.body(body) {
             Ok(request) => request,
             Err(err) => {
                 return Err(format!(
@@ -135,8 +367,8 @@ impl HttpClient {
             }
         };
 
-        match sonic_rs::from_slice::<Vec<CatResponse>>(response_body) {
-            Ok(cat_responses) => Ok(cat_responses),
+        match sonic_rs::from_slice::<T>(response_body) {
+            Ok(value) => Ok(value),
             Err(err) => Err(format!(
                 "An error occured while deserializing the HTTP response body, error: {}",
                 &err,
@@ -144,3 +376,71 @@ impl HttpClient {
         }
     }
 }
+
+impl CatResponse {
+    /// A cat of a provider which only knows the image URL, the last path segment of the URL is
+    /// used as ID when none is provided.
+    fn from_url(id: Option<String>, url: String) -> Self {
+        let id = id.unwrap_or_else(|| {
+            let name = url.rsplit('/').next().unwrap_or_default();
+
+            name.split_once('.')
+                .map_or(name, |(name, _)| name)
+                .to_string()
+        });
+
+        Self {
+            id,
+            url,
+            width: None,
+            height: None,
+            mime_type: None,
+            breeds: vec![],
+        }
+    }
+}
+
+/// Resolves a [JSON pointer], e.g. `/0/url`.
+///
+/// [JSON pointer]: https://datatracker.ietf.org/doc/html/rfc6901
+fn pointer<'a>(value: &'a Value, pointer: &str) -> Option<&'a Value> {
+    if pointer.is_empty() {
+        return Some(value);
+    }
+
+    pointer
+        .strip_prefix('/')?
+        .split('/')
+        .try_fold(value, |value, token| {
+            let token = token.replace("~1", "/").replace("~0", "~");
+
+            if value.is_array() {
+                value.get(token.parse::<usize>().ok()?)
+            } else {
+                value.get(token.as_str())
+            }
+        })
+}
+
+/// TheDogAPI breed IDs are numbers, TheCatAPI ones strings.
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(u64),
+    }
+
+    match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(id) => Ok(id),
+        StringOrNumber::Number(id) => Ok(id.to_string()),
+    }
+}
+
+/// Whether the ID, breed or image type can be put in the request URI as is.
+pub fn valid_parameter(parameter: &str) -> bool {
+    !parameter.is_empty()
+        && parameter
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+}
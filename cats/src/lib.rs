@@ -1,67 +1,93 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, VecDeque, hash_map::Entry},
     env,
     sync::{LazyLock, RwLock},
+    time::UNIX_EPOCH,
 };
 
-use serde::Deserialize;
+use plugin_sdk::{
+    discord_bot::plugin::{
+        discord_types::Requests,
+        host_functions::{discord_request, log},
+        host_types::LogLevels,
+        plugin_types::{
+            RegistrationsRequest, RegistrationsRequestDiscordEvents,
+            RegistrationsRequestInteractionCreate, RegistrationsResult, SupportedRegistrations,
+        },
+        storage,
+    },
+    embed,
+    error::{self, PluginError},
+    exports::discord_bot::plugin::plugin_functions::{DiscordEvents, Guest},
+    interaction,
+    locale::{self, Catalogs, DEFAULT_LOCALE, Locales},
+    registrations, request,
+    router::{ErrorEmbeds, Router},
+};
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
 use twilight_model::{
     application::{
-        command::{Command, CommandOption, CommandType},
+        command::{
+            Command, CommandOption, CommandOptionChoice, CommandOptionChoiceValue,
+            CommandOptionType, CommandType,
+        },
         interaction::{
             InteractionContextType, InteractionData, application_command::CommandOptionValue,
         },
     },
-    channel::message::Embed,
+    channel::message::{
+        Component, Embed, MessageFlags,
+        component::{ActionRow, Button, ButtonStyle},
+        embed::{EmbedField, EmbedImage},
+    },
     gateway::payload::incoming::{InteractionCreate, MessageCreate},
-    http::interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
+    http::interaction::InteractionResponseData,
     id::{
         Id,
         marker::{ChannelMarker, GuildMarker, UserMarker},
     },
     oauth::ApplicationIntegrationType,
 };
+use wstd::rand::get_insecure_random_bytes;
 
+mod dependency;
 mod http;
-use http::HttpClient;
-
-// Use a procedural macro to generate bindings for the world we specified in
-// `../wit/world.wit`
-wit_bindgen::generate!({ path: "../wit" });
-
-use crate::{
-    discord_bot::plugin::{
-        discord_types::Requests as DiscordRequests,
-        host_functions::discord_request,
-        plugin_types::{
-            RegistrationsRequest, RegistrationsRequestDiscordEvents,
-            RegistrationsRequestInteractionCreate, SupportedRegistrations,
-        },
-    },
-    exports::discord_bot::plugin::plugin_functions::{DiscordEvents, Guest},
-};
+use http::{CatQuery, CatResponse, CatResponseBreed, HttpClient, Provider};
 
 // Define a custom srtuct and implement the generated `Guest` trait for it which
 // represents implementing all the necessary exported interfaces for this
 // component. This type can also store plugin context.
 struct Plugin {
     http_client: HttpClient,
+    supported_registrations: RwLock<SupportedRegistrations>,
     storred_settings: RwLock<PluginStoredSettings>,
     stats: RwLock<PluginStats>,
+    registrations: RwLock<Option<RegistrationsRequest>>,
+    /// The time in seconds of the last cat message reply per channel.
+    cat_message_replies: RwLock<HashMap<Id<ChannelMarker>, u64>>,
+    /// Fetched once, breeds are rarely added.
+    breeds: RwLock<Option<Vec<CatResponseBreed>>>,
+    /// Prefetched cats for unfiltered interactions, refilled by the [`Plugin::CAT_POOL_JOB`] job.
+    cat_pool: RwLock<VecDeque<CatResponse>>,
 }
 
 struct PluginStoredSettings {
     cat_message_response_chance: u8,
+    cat_message_words: Vec<String>,
+    cat_message_cooldown: u64,
     automated_cats: Vec<PluginStoredSettingsAutomatedCat>,
     show_error_embeds: bool,
+    cat_pool_size: usize,
 }
 
 struct PluginStoredSettingsAutomatedCat {
-    id: u16,
-    guild: Id<GuildMarker>,
+    job: String,
     channel: Id<ChannelMarker>,
 }
 
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
 struct PluginStats {
     total_cats_requested: u32,
     cat_messages_detected: u32,
@@ -71,30 +97,123 @@ struct PluginStats {
 }
 
 // FIXME: Does not actually work with no settings
-#[derive(Debug, Deserialize)]
+/// Cats on demand, in reply to messages and on a schedule.
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 struct PluginSettings {
+    /// The chance in percent a message mentioning cats gets a cat as reply, 0 disables it.
     #[serde(default = "PluginSettings::cat_message_response_chance_default")]
+    #[schemars(range(max = 100))]
     cat_message_response_chance: u8,
+    /// The words which make a message mention cats, matched as whole words ignoring case.
+    #[serde(default = "PluginSettings::cat_message_words_default")]
+    cat_message_words: Vec<String>,
+    /// The minimum amount of seconds between cat replies in the same channel.
+    #[serde(default = "PluginSettings::cat_message_cooldown_default")]
+    cat_message_cooldown: u64,
+    /// Whether the /cat command is registered.
     #[serde(default = "PluginSettings::cats_on_demand_default")]
     cats_on_demand: bool,
+    /// Cats which get posted on a schedule.
     #[serde(default = "PluginSettings::automated_cats_default")]
     automated_cats: Vec<PluginSettingsAutomatedCat>,
+    /// The amount of cats which get prefetched so interactions do not wait on the image provider,
+    /// 0 disables it.
+    #[serde(default = "PluginSettings::cat_pool_size_default")]
+    #[schemars(range(max = 100))]
+    cat_pool_size: u8,
+    /// The cron expression the prefetched cats get refilled on, in a single request for TheCatAPI
+    /// compatible providers and one cat per run for the others.
+    #[serde(default = "PluginSettings::cat_pool_cron_default")]
+    cat_pool_cron: String,
+    /// Whether error embeds are shown to everyone, otherwise only the user who triggered the
+    /// interaction sees them.
     #[serde(default = "PluginSettings::show_error_embeds_default")]
     show_error_embeds: bool,
+    /// Where the images come from, filters, breeds and favourites need TheCatAPI or TheDogAPI.
+    #[serde(default = "PluginSettings::provider_default")]
+    provider: PluginSettingsProvider,
+    /// Overrides of the localized strings, keyed by locale and then by string key.
+    #[serde(default)]
+    strings: Catalogs,
+}
+
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum PluginSettingsProvider {
+    /// TheCatAPI, the API key falls back to the API_KEY environment variable. Works without a key
+    /// with a lower rate limit.
+    TheCatApi {
+        #[serde(default = "PluginSettingsProvider::the_cat_api_base_url_default")]
+        base_url: String,
+        #[serde(default)]
+        api_key: Option<String>,
+    },
+    /// TheDogAPI, or any other API with the same endpoints when the base URL is changed.
+    TheDogApi {
+        #[serde(default = "PluginSettingsProvider::the_dog_api_base_url_default")]
+        base_url: String,
+        #[serde(default)]
+        api_key: Option<String>,
+    },
+    /// Any endpoint returning JSON which contains an image URL.
+    Json {
+        url: String,
+        /// Sent with every request, e.g. an authorization header.
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        /// The JSON pointer to the image URL, e.g. `/0/url`.
+        url_pointer: String,
+        /// The JSON pointer to the image ID, the file name of the image URL is used when not
+        /// provided.
+        #[serde(default)]
+        id_pointer: Option<String>,
+    },
+    /// A random image of the list.
+    Static { urls: Vec<String> },
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 struct PluginSettingsAutomatedCat {
+    /// The ID of the guild the channel is in.
+    #[schemars(with = "String")]
     guild_id: Id<GuildMarker>,
+    /// The ID of the channel cats get posted in.
+    #[schemars(with = "String")]
     channel_id: Id<ChannelMarker>,
+    /// The cron expression the cats get posted on.
     cron: String,
 }
 
+impl PluginSettingsAutomatedCat {
+    const JOB_PREFIX: &str = "automated_cat_";
+
+    fn job(&self) -> String {
+        format!("{}{}_{}", Self::JOB_PREFIX, self.guild_id, self.channel_id)
+    }
+}
+
+impl PluginStats {
+    const STORAGE_KEY: &str = "stats";
+    /// The custom ID prefix of the leaderboard page buttons, followed by the page index.
+    const PAGE_COMPONENT: &str = "cat-stats:";
+    const PAGE_SIZE: usize = 10;
+}
+
 impl PluginSettings {
     fn cat_message_response_chance_default() -> u8 {
         0
     }
 
+    fn cat_message_words_default() -> Vec<String> {
+        ["cat", "cats", "kitty", "kitties", "kitten", "kittens"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    fn cat_message_cooldown_default() -> u64 {
+        60
+    }
+
     fn cats_on_demand_default() -> bool {
         true
     }
@@ -103,17 +222,78 @@ impl PluginSettings {
         vec![]
     }
 
+    fn cat_pool_size_default() -> u8 {
+        10
+    }
+
+    fn cat_pool_cron_default() -> String {
+        String::from("0 * * * * *")
+    }
+
     fn show_error_embeds_default() -> bool {
         true
     }
+
+    fn provider_default() -> PluginSettingsProvider {
+        PluginSettingsProvider::TheCatApi {
+            base_url: PluginSettingsProvider::the_cat_api_base_url_default(),
+            api_key: None,
+        }
+    }
+}
+
+impl PluginSettingsProvider {
+    fn the_cat_api_base_url_default() -> String {
+        String::from("https://api.thecatapi.com/v1/")
+    }
+
+    fn the_dog_api_base_url_default() -> String {
+        String::from("https://api.thedogapi.com/v1/")
+    }
+
+    fn provider(&self) -> Result<Provider, String> {
+        match self {
+            Self::TheCatApi { base_url, api_key } | Self::TheDogApi { base_url, api_key } => {
+                let mut base_url = base_url.clone();
+
+                if !base_url.ends_with('/') {
+                    base_url.push('/');
+                }
+
+                Ok(Provider::CatApi {
+                    base_url,
+                    api_key: api_key.clone().or_else(|| env::var("API_KEY").ok()),
+                })
+            }
+            Self::Json {
+                url,
+                headers,
+                url_pointer,
+                id_pointer,
+            } => Ok(Provider::Json {
+                url: url.clone(),
+                headers: headers.clone().into_iter().collect(),
+                url_pointer: url_pointer.clone(),
+                id_pointer: id_pointer.clone(),
+            }),
+            Self::Static { urls } if urls.is_empty() => Err(String::from(
+                "The static image provider needs at least one URL.",
+            )),
+            Self::Static { urls } => Ok(Provider::Static { urls: urls.clone() }),
+        }
+    }
 }
 
 static CONTEXT: LazyLock<Plugin> = LazyLock::new(|| Plugin {
     http_client: HttpClient::new(),
+    supported_registrations: RwLock::new(SupportedRegistrations::empty()),
     storred_settings: RwLock::new(PluginStoredSettings {
         cat_message_response_chance: 0,
+        cat_message_words: vec![],
+        cat_message_cooldown: 0,
         automated_cats: vec![],
         show_error_embeds: true,
+        cat_pool_size: 0,
     }),
     stats: RwLock::new(PluginStats {
         total_cats_requested: 0,
@@ -122,6 +302,38 @@ static CONTEXT: LazyLock<Plugin> = LazyLock::new(|| Plugin {
         automated_cats: 0,
         most_cats_demanded: BTreeMap::new(),
     }),
+    registrations: RwLock::new(None),
+    cat_message_replies: RwLock::new(HashMap::new()),
+    breeds: RwLock::new(None),
+    cat_pool: RwLock::new(VecDeque::new()),
+});
+
+static LOCALES: LazyLock<Locales> = LazyLock::new(|| plugin_sdk::locales!("en-US", "nl"));
+
+static ROUTER: LazyLock<Router> = LazyLock::new(|| {
+    Router::new()
+        .locales(&LOCALES)
+        .command("cat", Plugin::cat_command)
+        .command("cat-stats", Plugin::cat_stats_command)
+        .command("cat-breed", Plugin::cat_breed_command)
+        .autocomplete("cat", Plugin::breed_autocomplete)
+        .autocomplete("cat-breed", Plugin::breed_autocomplete)
+        .command("cat-favourites", Plugin::cat_favourites_command)
+        .component(PluginStats::PAGE_COMPONENT, Plugin::cat_stats_page)
+        .component(Plugin::ANOTHER_CAT_COMPONENT, Plugin::another_cat)
+        .component(Plugin::FAVOURITE_COMPONENT, Plugin::favourite_cat)
+        .component(Plugin::SHOW_BREED_COMPONENT, Plugin::show_breed)
+        .component(
+            Plugin::FAVOURITES_PAGE_COMPONENT,
+            Plugin::cat_favourites_page,
+        )
+        .error_embeds(|| {
+            if CONTEXT.storred_settings.read().unwrap().show_error_embeds {
+                ErrorEmbeds::Public
+            } else {
+                ErrorEmbeds::Ephemeral
+            }
+        })
 });
 
 impl Guest for Plugin {
@@ -129,13 +341,113 @@ impl Guest for Plugin {
         settings: Vec<u8>,
         supported_registrations: SupportedRegistrations,
     ) -> Result<RegistrationsRequest, String> {
-        if env::var("API_KEY").is_err() {
-            return Err(String::from(
-                "The API_KEY environment variable was not provided",
-            ));
+        *CONTEXT.supported_registrations.write().unwrap() = supported_registrations;
+
+        let settings = Plugin::update_settings(&settings)?;
+
+        *CONTEXT.stats.write().unwrap() = Plugin::load_stats();
+
+        let registrations = Plugin::registrations(settings);
+
+        *CONTEXT.registrations.write().unwrap() = Some(registrations.clone());
+
+        Ok(registrations)
+    }
+
+    fn settings_update(settings: Vec<u8>) -> Result<RegistrationsRequest, String> {
+        let settings = Plugin::update_settings(&settings)?;
+
+        let registrations = Plugin::registrations(settings);
+
+        let previous_registrations = CONTEXT
+            .registrations
+            .write()
+            .unwrap()
+            .replace(registrations.clone());
+
+        Ok(registrations::diff(previous_registrations, registrations))
+    }
+
+    fn settings_schema() -> Vec<u8> {
+        sonic_rs::to_vec(&schema_for!(PluginSettings)).unwrap()
+    }
+
+    fn registrations_processed(registrations_result: RegistrationsResult) -> Result<(), String> {
+        // Rejected jobs never run, so their automated cats are forgotten
+        CONTEXT
+            .storred_settings
+            .write()
+            .unwrap()
+            .automated_cats
+            .retain(|automated_cat| {
+                !registrations_result
+                    .scheduled_jobs
+                    .iter()
+                    .any(|(job, result)| *job == automated_cat.job && result.is_err())
+            });
+
+        registrations::log_result(registrations_result);
+
+        Ok(())
+    }
+
+    fn shutdown() -> Result<(), String> {
+        Ok(())
+    }
+
+    fn discord_event(event: DiscordEvents) -> Result<(), String> {
+        match event {
+            DiscordEvents::InteractionCreate(interaction_create) => {
+                ROUTER.handle(&interaction_create)
+            }
+            DiscordEvents::MessageCreate(message) => {
+                let result = error::deserialize::<Box<MessageCreate>>(&message, "message")
+                    .and_then(|message| CONTEXT.cat_message(&message));
+
+                if let Err(err) = result {
+                    err.log();
+                }
+
+                Ok(())
+            }
+            _ => Err(String::from(
+                "The plugin received an event it did not register.",
+            )),
+        }
+    }
+
+    fn scheduled_job(job: String, _payload: Option<Vec<u8>>) -> Result<(), String> {
+        match job.as_str() {
+            Plugin::CAT_POOL_JOB => Ok(CONTEXT.refill_cat_pool()?),
+            job if job.starts_with(PluginSettingsAutomatedCat::JOB_PREFIX) => {
+                Ok(CONTEXT.automated_cat(job)?)
+            }
+            job => Err(format!("Unknown scheduled job: {job}")),
         }
+    }
+
+    fn dependency_function(function: String, params: Vec<u8>) -> Result<Vec<u8>, String> {
+        match function.as_str() {
+            "request_cat" => dependency::request_cat(&params),
+            function => Err(format!(
+                "The {function} dependency function does not exist."
+            )),
+        }
+    }
+}
+
+impl Plugin {
+    /// The custom ID prefixes of the cat message buttons, followed by the breed filter, the image
+    /// ID and the breed ID.
+    const ANOTHER_CAT_COMPONENT: &str = "cat-another:";
+    const FAVOURITE_COMPONENT: &str = "cat-favourite:";
+    const SHOW_BREED_COMPONENT: &str = "cat-show-breed:";
+    /// Followed by the user ID, a colon and the page index.
+    const FAVOURITES_PAGE_COMPONENT: &str = "cat-favourites:";
+    const CAT_POOL_JOB: &str = "cat_pool";
 
-        let settings = match sonic_rs::from_slice::<PluginSettings>(&settings) {
+    fn update_settings(settings: &[u8]) -> Result<PluginSettings, String> {
+        let settings = match sonic_rs::from_slice::<PluginSettings>(settings) {
             Ok(settings) => settings,
             Err(err) => {
                 return Err(format!(
@@ -145,9 +457,79 @@ impl Guest for Plugin {
             }
         };
 
-        // Write settings to CONTEXT and use that.
+        // Validated before anything is applied, so invalid settings do not get applied halfway
+        let provider = settings.provider.provider()?;
+
+        CONTEXT
+            .storred_settings
+            .write()
+            .unwrap()
+            .cat_message_response_chance = settings.cat_message_response_chance;
+
+        CONTEXT.storred_settings.write().unwrap().cat_message_words = settings
+            .cat_message_words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect();
+
+        CONTEXT
+            .storred_settings
+            .write()
+            .unwrap()
+            .cat_message_cooldown = settings.cat_message_cooldown;
+
+        CONTEXT.storred_settings.write().unwrap().show_error_embeds = settings.show_error_embeds;
+
+        // The pool only gets refilled through the scheduled job
+        let cat_pool_size = if CONTEXT
+            .supported_registrations
+            .read()
+            .unwrap()
+            .contains(SupportedRegistrations::SCHEDULED_JOBS)
+        {
+            usize::from(settings.cat_pool_size)
+        } else {
+            0
+        };
+
+        CONTEXT.storred_settings.write().unwrap().cat_pool_size = cat_pool_size;
+
+        CONTEXT.storred_settings.write().unwrap().automated_cats = if CONTEXT
+            .supported_registrations
+            .read()
+            .unwrap()
+            .contains(SupportedRegistrations::SCHEDULED_JOBS)
+        {
+            settings
+                .automated_cats
+                .iter()
+                .map(|automated_cat| PluginStoredSettingsAutomatedCat {
+                    job: automated_cat.job(),
+                    channel: automated_cat.channel_id,
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        // The pooled cats and cached breeds are of the previous provider
+        if CONTEXT.http_client.set_provider(provider) {
+            CONTEXT.cat_pool.write().unwrap().clear();
+            *CONTEXT.breeds.write().unwrap() = None;
+        }
+
+        LOCALES.set_overrides(settings.strings.clone());
+
+        Ok(settings)
+    }
+
+    fn registrations(settings: PluginSettings) -> RegistrationsRequest {
+        let supported_registrations = *CONTEXT.supported_registrations.read().unwrap();
+
+        let supports_cat_api = CONTEXT.http_client.supports_cat_api();
 
         let mut commands = vec![];
+        let mut components = vec![];
 
         if supported_registrations
             .contains(SupportedRegistrations::DISCORD_EVENT_INTERACTION_CREATE)
@@ -164,8 +546,8 @@ impl Guest for Plugin {
                     default_member_permissions: None,
                     #[allow(deprecated)]
                     dm_permission: None,
-                    description: String::from("Request a cat"),
-                    description_localizations: None,
+                    description: LOCALES.text(DEFAULT_LOCALE, "command.cat.description"),
+                    description_localizations: LOCALES.localizations("command.cat.description"),
                     guild_id: None,
                     id: None,
                     integration_types: Some(vec![
@@ -174,210 +556,1117 @@ impl Guest for Plugin {
                     ]),
                     kind: CommandType::ChatInput,
                     name: String::from("cat"),
-                    name_localizations: None,
+                    name_localizations: LOCALES.localizations("command.cat.name"),
+                    nsfw: Some(false),
+                    options: if supports_cat_api {
+                        vec![
+                            CommandOption {
+                                autocomplete: None,
+                                channel_types: None,
+                                choices: None,
+                                description: LOCALES
+                                    .text(DEFAULT_LOCALE, "command.cat.id.description"),
+                                description_localizations: LOCALES
+                                    .localizations("command.cat.id.description"),
+                                kind: CommandOptionType::String,
+                                max_length: None,
+                                max_value: None,
+                                min_length: None,
+                                min_value: None,
+                                name: String::from("id"),
+                                name_localizations: LOCALES.localizations("command.cat.id.name"),
+                                options: None,
+                                required: Some(false),
+                            },
+                            Self::breed_option("cat", false),
+                        ]
+                    } else {
+                        vec![]
+                    },
+                    version: Id::new(1),
+                })
+                .unwrap(),
+            );
+        }
+
+        if supported_registrations
+            .contains(SupportedRegistrations::DISCORD_EVENT_INTERACTION_CREATE)
+        {
+            commands.push(
+                sonic_rs::to_vec(&Command {
+                    application_id: None,
+                    contexts: Some(vec![
+                        InteractionContextType::Guild,
+                        InteractionContextType::BotDm,
+                        InteractionContextType::PrivateChannel,
+                    ]),
+                    default_member_permissions: None,
+                    #[allow(deprecated)]
+                    dm_permission: None,
+                    description: LOCALES.text(DEFAULT_LOCALE, "command.cat-stats.description"),
+                    description_localizations: LOCALES
+                        .localizations("command.cat-stats.description"),
+                    guild_id: None,
+                    id: None,
+                    integration_types: Some(vec![
+                        ApplicationIntegrationType::GuildInstall,
+                        ApplicationIntegrationType::UserInstall,
+                    ]),
+                    kind: CommandType::ChatInput,
+                    name: String::from("cat-stats"),
+                    name_localizations: LOCALES.localizations("command.cat-stats.name"),
+                    nsfw: Some(false),
+                    options: vec![],
+                    version: Id::new(1),
+                })
+                .unwrap(),
+            );
+        }
+
+        if supported_registrations
+            .contains(SupportedRegistrations::DISCORD_EVENT_INTERACTION_CREATE)
+            && supports_cat_api
+        {
+            commands.push(
+                sonic_rs::to_vec(&Command {
+                    application_id: None,
+                    contexts: Some(vec![
+                        InteractionContextType::Guild,
+                        InteractionContextType::BotDm,
+                        InteractionContextType::PrivateChannel,
+                    ]),
+                    default_member_permissions: None,
+                    #[allow(deprecated)]
+                    dm_permission: None,
+                    description: LOCALES.text(DEFAULT_LOCALE, "command.cat-breed.description"),
+                    description_localizations: LOCALES
+                        .localizations("command.cat-breed.description"),
+                    guild_id: None,
+                    id: None,
+                    integration_types: Some(vec![
+                        ApplicationIntegrationType::GuildInstall,
+                        ApplicationIntegrationType::UserInstall,
+                    ]),
+                    kind: CommandType::ChatInput,
+                    name: String::from("cat-breed"),
+                    name_localizations: LOCALES.localizations("command.cat-breed.name"),
+                    nsfw: Some(false),
+                    options: vec![Self::breed_option("cat-breed", true)],
+                    version: Id::new(1),
+                })
+                .unwrap(),
+            );
+
+            commands.push(
+                sonic_rs::to_vec(&Command {
+                    application_id: None,
+                    contexts: Some(vec![
+                        InteractionContextType::Guild,
+                        InteractionContextType::BotDm,
+                        InteractionContextType::PrivateChannel,
+                    ]),
+                    default_member_permissions: None,
+                    #[allow(deprecated)]
+                    dm_permission: None,
+                    description: LOCALES.text(DEFAULT_LOCALE, "command.cat-favourites.description"),
+                    description_localizations: LOCALES
+                        .localizations("command.cat-favourites.description"),
+                    guild_id: None,
+                    id: None,
+                    integration_types: Some(vec![
+                        ApplicationIntegrationType::GuildInstall,
+                        ApplicationIntegrationType::UserInstall,
+                    ]),
+                    kind: CommandType::ChatInput,
+                    name: String::from("cat-favourites"),
+                    name_localizations: LOCALES.localizations("command.cat-favourites.name"),
                     nsfw: Some(false),
-                    options: vec![CommandOption {
-                        autocomplete: None,
-                        channel_types: None,
-                        choices: None,
-                        description: String::from("The ID of the requested cat"),
-                        description_localizations: None,
-                        kind: twilight_model::application::command::CommandOptionType::String,
-                        max_length: None,
-                        max_value: None,
-                        min_length: None,
-                        min_value: None,
-                        name: String::from("id"),
-                        name_localizations: None,
-                        options: None,
-                        required: Some(false),
-                    }],
+                    options: vec![],
                     version: Id::new(1),
                 })
                 .unwrap(),
             );
         }
 
-        let mut scheduled_jobs = HashMap::new();
+        if supported_registrations
+            .contains(SupportedRegistrations::DISCORD_EVENT_INTERACTION_CREATE)
+        {
+            components.extend(
+                [
+                    PluginStats::PAGE_COMPONENT,
+                    Self::ANOTHER_CAT_COMPONENT,
+                    Self::FAVOURITE_COMPONENT,
+                    Self::SHOW_BREED_COMPONENT,
+                    Self::FAVOURITES_PAGE_COMPONENT,
+                ]
+                .map(String::from),
+            );
+        }
+
+        let mut scheduled_jobs = BTreeMap::new();
 
-        for automated_cat in settings.automated_cats {
-            // TODO: Write successful items to CONTEXT
-            scheduled_jobs
-                .entry(format!(
-                    "automated_cat_{}_{}",
-                    &automated_cat.guild_id, &automated_cat.channel_id
-                ))
-                .or_insert(vec![])
-                .push(automated_cat.cron);
+        if supported_registrations.contains(SupportedRegistrations::SCHEDULED_JOBS) {
+            if settings.cat_pool_size != 0 {
+                scheduled_jobs.insert(
+                    String::from(Self::CAT_POOL_JOB),
+                    vec![settings.cat_pool_cron],
+                );
+            }
+
+            for automated_cat in settings.automated_cats {
+                scheduled_jobs
+                    .entry(automated_cat.job())
+                    .or_insert(vec![])
+                    .push(automated_cat.cron);
+            }
         }
 
-        Ok(RegistrationsRequest {
+        RegistrationsRequest {
             discord_events: Some(RegistrationsRequestDiscordEvents {
                 interaction_create: Some(RegistrationsRequestInteractionCreate {
                     application_commands: Some(commands),
-                    message_components: None,
+                    message_components: Some(components),
                     modals: None,
                 }),
                 message_create: supported_registrations
                     .contains(SupportedRegistrations::DISCORD_EVENT_MESSAGE_CREATE)
                     && settings.cat_message_response_chance != 0,
-                thread_create: false,
-                thread_delete: false,
-                thread_list_sync: false,
-                thread_member_update: false,
-                thread_members_update: false,
-                thread_update: false,
+                ..registrations::discord_events()
             }),
             scheduled_jobs: Some(scheduled_jobs.into_iter().collect()),
             dependency_functions: Some(vec![String::from("request_cat")]),
+        }
+    }
+
+    fn cat_command(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Some(InteractionData::ApplicationCommand(command_data)) =
+            interaction_create.data.as_ref()
+        else {
+            return Err(PluginError::Missing("application command data"));
+        };
+
+        let option = |name: &str| {
+            command_data
+                .options
+                .iter()
+                .find(|option| option.name == name)
+                .and_then(|option| match &option.value {
+                    CommandOptionValue::String(value) => Some(value.clone()),
+                    _ => None,
+                })
+        };
+
+        CONTEXT.cat_on_demand(
+            interaction_create,
+            CatQuery {
+                id: option("id"),
+                breed: option("breed"),
+                ..Default::default()
+            },
+            false,
+        )
+    }
+
+    /// Responds with a cat for the user who triggered the interaction and counts it in the stats.
+    /// Unfiltered cats come from the pool, when it is empty or the query is filtered the
+    /// interaction is deferred while the cat is requested. The breed filter can be a typed breed
+    /// name. update replaces the message of the component instead of replying.
+    fn cat_on_demand(
+        &self,
+        interaction_create: &InteractionCreate,
+        mut query: CatQuery,
+        update: bool,
+    ) -> Result<(), PluginError> {
+        let pooled_cat = if query.filtered() {
+            None
+        } else {
+            self.cat_pool.write().unwrap().pop_front()
+        };
+
+        let deferred = pooled_cat.is_none();
+
+        if deferred {
+            if update {
+                interaction::defer_update(interaction_create)?;
+            } else {
+                interaction::defer(interaction_create)?;
+            }
+        }
+
+        // Filtered queries are always deferred, so the breeds can be requested here
+        if let Some(breed) = &query.breed {
+            query.breed = Some(self.find_breed(locale::of(interaction_create), breed)?.id);
+        }
+
+        let cat_response = match pooled_cat {
+            Some(cat) => Ok(vec![cat]),
+            None => self.http_client.request_cat(&query),
+        };
+
+        let Some(cat) = cat_response?.pop() else {
+            return Err(PluginError::Missing("cat"));
+        };
+
+        self.update_stats(|stats| {
+            stats.total_cats_requested += 1;
+
+            if let Some(user_id) = interaction_create.author_id() {
+                stats.cats_on_demand += 1;
+                *stats.most_cats_demanded.entry(user_id).or_insert(0) += 1;
+            }
+        });
+
+        let locale = locale::of(interaction_create);
+
+        let components = Self::cat_components(locale, &cat, query.breed.as_deref());
+
+        let embed = Self::cat_embed(
+            embed::requested_by(&LOCALES, interaction_create),
+            locale,
+            cat,
+        );
+
+        if deferred {
+            let body = request::body(
+                request::client()
+                    .interaction(interaction_create.application_id)
+                    .update_response(&interaction_create.token)
+                    .components(Some(&[components]))
+                    .embeds(Some(&[embed])),
+            )?;
+
+            interaction::update_original(interaction_create, body)?;
+        } else {
+            let data = InteractionResponseData {
+                components: Some(vec![components]),
+                embeds: Some(vec![embed]),
+                ..Default::default()
+            };
+
+            if update {
+                interaction::update_message(interaction_create, data)?;
+            } else {
+                interaction::reply(interaction_create, data)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the cat of the message with a new one of the same breed filter.
+    fn another_cat(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let breed = Self::component_value(interaction_create, Self::ANOTHER_CAT_COMPONENT)?;
+
+        CONTEXT.cat_on_demand(
+            interaction_create,
+            CatQuery {
+                breed: (!breed.is_empty()).then(|| breed.to_string()),
+                ..Default::default()
+            },
+            true,
+        )
+    }
+
+    /// Tops the pool up to its size, in one batch request for TheCatAPI compatible providers.
+    fn refill_cat_pool(&self) -> Result<(), PluginError> {
+        let cat_pool_size = self.storred_settings.read().unwrap().cat_pool_size;
+
+        let missing = cat_pool_size.saturating_sub(self.cat_pool.read().unwrap().len());
+
+        if missing == 0 {
+            return Ok(());
+        }
+
+        let cat_response = self.http_client.request_cat(&CatQuery {
+            limit: u8::try_from(missing).unwrap_or(u8::MAX),
+            ..Default::default()
+        });
+
+        let mut cat_pool = self.cat_pool.write().unwrap();
+
+        let missing = cat_pool_size.saturating_sub(cat_pool.len());
+
+        cat_pool.extend(cat_response?.into_iter().take(missing));
+
+        Ok(())
+    }
+
+    fn favourite_cat(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let image_id = Self::component_value(interaction_create, Self::FAVOURITE_COMPONENT)?;
+
+        let Some(user_id) = interaction_create.author_id() else {
+            return Err(PluginError::Missing("interaction author"));
+        };
+
+        CONTEXT
+            .http_client
+            .favourite(image_id, &user_id.to_string())?;
+
+        interaction::reply(
+            interaction_create,
+            InteractionResponseData {
+                content: Some(LOCALES.text(locale::of(interaction_create), "reply.favourited")),
+                flags: Some(MessageFlags::EPHEMERAL),
+                ..Default::default()
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn show_breed(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let breed_id = Self::component_value(interaction_create, Self::SHOW_BREED_COMPONENT)?;
+
+        let breed = CONTEXT.find_breed(locale::of(interaction_create), breed_id)?;
+
+        interaction::reply(
+            interaction_create,
+            InteractionResponseData {
+                embeds: Some(vec![Self::breed_embed(interaction_create, breed)]),
+                flags: Some(MessageFlags::EPHEMERAL),
+                ..Default::default()
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn cat_favourites_command(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Some(user_id) = interaction_create.author_id() else {
+            return Err(PluginError::Missing("interaction author"));
+        };
+
+        let data = Self::favourites_page(interaction_create, user_id, 0)?;
+
+        interaction::reply(
+            interaction_create,
+            InteractionResponseData {
+                flags: Some(MessageFlags::EPHEMERAL),
+                ..data
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn cat_favourites_page(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let value = Self::component_value(interaction_create, Self::FAVOURITES_PAGE_COMPONENT)?;
+
+        let Some((user_id, page)) = value.split_once(':').and_then(|(user_id, page)| {
+            Some((
+                Id::<UserMarker>::new_checked(user_id.parse().ok()?)?,
+                page.parse::<usize>().ok()?,
+            ))
+        }) else {
+            return Err(PluginError::Missing("favourites page"));
+        };
+
+        interaction::update_message(
+            interaction_create,
+            Self::favourites_page(interaction_create, user_id, page)?,
+        )?;
+
+        Ok(())
+    }
+
+    /// One favourite per page, pages past the end show the last favourite.
+    fn favourites_page(
+        interaction_create: &InteractionCreate,
+        user_id: Id<UserMarker>,
+        page: usize,
+    ) -> Result<InteractionResponseData, PluginError> {
+        let locale = locale::of(interaction_create);
+
+        let mut favourites = CONTEXT
+            .http_client
+            .request_favourites(&user_id.to_string())?;
+
+        let mut embed = embed::requested_by(&LOCALES, interaction_create);
+
+        if favourites.is_empty() {
+            embed.title = Some(LOCALES.text(locale, "embed.cat-favourites.empty-title"));
+            embed.description = Some(LOCALES.text(locale, "embed.cat-favourites.empty"));
+
+            return Ok(InteractionResponseData {
+                components: Some(vec![]),
+                embeds: Some(vec![embed]),
+                ..Default::default()
+            });
+        }
+
+        let pages = favourites.len();
+        let page = page.min(pages - 1);
+
+        let favourite = favourites.swap_remove(page);
+
+        embed.title = Some(LOCALES.format(
+            locale,
+            "embed.cat-favourites.title",
+            &[("page", &(page + 1)), ("pages", &pages)],
+        ));
+        embed.image = favourite
+            .image
+            .and_then(|image| image.url)
+            .map(|url| EmbedImage {
+                height: None,
+                proxy_url: None,
+                url,
+                width: None,
+            });
+
+        Ok(InteractionResponseData {
+            components: Some(vec![Self::page_buttons(
+                locale,
+                &format!("{}{user_id}:", Self::FAVOURITES_PAGE_COMPONENT),
+                page,
+                pages,
+            )]),
+            embeds: Some(vec![embed]),
+            ..Default::default()
         })
     }
 
-    fn shutdown() -> Result<(), String> {
-        todo!();
+    /// Another cat, favourite and show breed buttons, breed is the breed filter another cat
+    /// keeps.
+    fn cat_components(locale: &str, cat: &CatResponse, breed: Option<&str>) -> Component {
+        let mut components = vec![Self::button(
+            locale,
+            "button.another-cat",
+            format!(
+                "{}{}",
+                Self::ANOTHER_CAT_COMPONENT,
+                breed.unwrap_or_default()
+            ),
+            ButtonStyle::Primary,
+            false,
+        )];
+
+        // Only TheCatAPI compatible providers know about favourites and breeds
+        if CONTEXT.http_client.supports_cat_api() {
+            let cat_breed = cat.breeds.first().map(|breed| breed.id.as_str());
+
+            components.push(Self::button(
+                locale,
+                "button.favourite",
+                format!("{}{}", Self::FAVOURITE_COMPONENT, cat.id),
+                ButtonStyle::Secondary,
+                false,
+            ));
+            components.push(Self::button(
+                locale,
+                "button.show-breed",
+                format!(
+                    "{}{}",
+                    Self::SHOW_BREED_COMPONENT,
+                    cat_breed.unwrap_or_default()
+                ),
+                ButtonStyle::Secondary,
+                cat_breed.is_none(),
+            ));
+        }
+
+        Component::ActionRow(ActionRow {
+            id: None,
+            components,
+        })
     }
 
-    fn discord_event(event: DiscordEvents) -> Result<(), String> {
-        match event {
-            DiscordEvents::InteractionCreate(interaction) => {
-                let interaction =
-                    Box::new(sonic_rs::from_slice::<InteractionCreate>(&interaction).unwrap());
-
-                match interaction.data.as_ref() {
-                    Some(InteractionData::ApplicationCommand(command_data)) => {
-                        match command_data.name.as_str() {
-                            "cat" => {
-                                CONTEXT.cat_command(interaction);
-                                Ok(())
-                            }
-                            &_ => unimplemented!(),
-                        }
-                    }
-                    _ => unimplemented!(),
-                }
+    /// The breed option of the provided command, autocompleted with [`Plugin::breed_autocomplete`].
+    fn breed_option(command: &str, required: bool) -> CommandOption {
+        let key = format!("command.{command}.breed");
+
+        CommandOption {
+            autocomplete: Some(true),
+            channel_types: None,
+            choices: None,
+            description: LOCALES.text(DEFAULT_LOCALE, &format!("{key}.description")),
+            description_localizations: LOCALES.localizations(&format!("{key}.description")),
+            kind: CommandOptionType::String,
+            max_length: None,
+            max_value: None,
+            min_length: None,
+            min_value: None,
+            name: String::from("breed"),
+            name_localizations: LOCALES.localizations(&format!("{key}.name")),
+            options: None,
+            required: Some(required),
+        }
+    }
+
+    /// Suggests the breeds of which the name contains the focused value.
+    fn breed_autocomplete(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Some(InteractionData::ApplicationCommand(command_data)) =
+            interaction_create.data.as_ref()
+        else {
+            return Err(PluginError::Missing("autocomplete data"));
+        };
+
+        let query = command_data
+            .options
+            .iter()
+            .find_map(|option| match &option.value {
+                CommandOptionValue::Focused(value, _) => Some(value.to_lowercase()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let choices = CONTEXT
+            .breeds()?
+            .into_iter()
+            .filter(|breed| breed.name.to_lowercase().contains(&query) || breed.id == query)
+            .take(25)
+            .map(|breed| CommandOptionChoice {
+                name: breed.name,
+                name_localizations: None,
+                value: CommandOptionChoiceValue::String(breed.id),
+            })
+            .collect();
+
+        interaction::autocomplete(interaction_create, choices)?;
+
+        Ok(())
+    }
+
+    fn cat_breed_command(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Some(InteractionData::ApplicationCommand(command_data)) =
+            interaction_create.data.as_ref()
+        else {
+            return Err(PluginError::Missing("application command data"));
+        };
+
+        let Some(requested_breed) =
+            command_data
+                .options
+                .iter()
+                .find_map(|option| match &option.value {
+                    CommandOptionValue::String(breed) if option.name == "breed" => Some(breed),
+                    _ => None,
+                })
+        else {
+            return Err(PluginError::Missing("breed option"));
+        };
+
+        let breed = CONTEXT.find_breed(locale::of(interaction_create), requested_breed)?;
+
+        let embed = Self::breed_embed(interaction_create, breed);
+
+        interaction::reply(
+            interaction_create,
+            InteractionResponseData {
+                embeds: Some(vec![embed]),
+                ..Default::default()
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// The breed is an ID when picked from the suggestions, otherwise it is what the user typed.
+    fn find_breed(
+        &self,
+        locale: &str,
+        requested_breed: &str,
+    ) -> Result<CatResponseBreed, PluginError> {
+        match self.breeds()?.into_iter().find(|breed| {
+            breed.id == requested_breed || breed.name.eq_ignore_ascii_case(requested_breed)
+        }) {
+            Some(breed) => Ok(breed),
+            None => Err(PluginError::Other(LOCALES.format(
+                locale,
+                "error.unknown-breed",
+                &[("breed", &requested_breed)],
+            ))),
+        }
+    }
+
+    /// The temperament, origin, life span, weight and attribute scores of the breed.
+    fn breed_embed(interaction_create: &InteractionCreate, breed: CatResponseBreed) -> Embed {
+        let locale = locale::of(interaction_create);
+
+        let mut embed = embed::requested_by(&LOCALES, interaction_create);
+
+        let field = |key: &str, value: String, inline: bool| EmbedField {
+            inline,
+            name: LOCALES.text(locale, key),
+            value,
+        };
+
+        embed.title = Some(breed.name);
+        embed.description = breed.description;
+        embed.url = breed.wikipedia_url;
+        embed.image = breed
+            .image
+            .and_then(|image| image.url)
+            .map(|url| EmbedImage {
+                height: None,
+                proxy_url: None,
+                url,
+                width: None,
+            });
+
+        if let Some(origin) = breed.origin {
+            embed
+                .fields
+                .push(field("embed.cat-breed.origin", origin, true));
+        }
+
+        if let Some(life_span) = breed.life_span {
+            embed.fields.push(field(
+                "embed.cat-breed.life-span",
+                LOCALES.format(
+                    locale,
+                    "embed.cat-breed.life-span-value",
+                    &[("life_span", &life_span)],
+                ),
+                true,
+            ));
+        }
+
+        if let Some(weight) = breed.weight {
+            embed.fields.push(field(
+                "embed.cat-breed.weight",
+                LOCALES.format(
+                    locale,
+                    "embed.cat-breed.weight-value",
+                    &[("weight", &weight.metric)],
+                ),
+                true,
+            ));
+        }
+
+        if let Some(temperament) = breed.temperament {
+            embed
+                .fields
+                .push(field("embed.cat-breed.temperament", temperament, false));
+        }
+
+        let attributes = [
+            ("adaptability", breed.adaptability),
+            ("affection-level", breed.affection_level),
+            ("child-friendly", breed.child_friendly),
+            ("dog-friendly", breed.dog_friendly),
+            ("energy-level", breed.energy_level),
+            ("grooming", breed.grooming),
+            ("health-issues", breed.health_issues),
+            ("intelligence", breed.intelligence),
+            ("shedding-level", breed.shedding_level),
+            ("social-needs", breed.social_needs),
+            ("stranger-friendly", breed.stranger_friendly),
+            ("vocalisation", breed.vocalisation),
+        ]
+        .into_iter()
+        .filter_map(|(attribute, score)| {
+            // Scores go from 1 to 5
+            let score = usize::from(score?.min(5));
+
+            Some(format!(
+                "{}: {}{}",
+                LOCALES.text(locale, &format!("embed.cat-breed.attribute.{attribute}")),
+                "★".repeat(score),
+                "☆".repeat(5 - score)
+            ))
+        })
+        .collect::<Vec<_>>();
+
+        if !attributes.is_empty() {
+            embed.fields.push(field(
+                "embed.cat-breed.attributes",
+                attributes.join("\n"),
+                false,
+            ));
+        }
+
+        embed
+    }
+
+    /// The cached breeds, fetched on first use.
+    fn breeds(&self) -> Result<Vec<CatResponseBreed>, PluginError> {
+        if let Some(breeds) = self.breeds.read().unwrap().as_ref() {
+            return Ok(breeds.clone());
+        }
+
+        let breeds = self.http_client.request_breeds()?;
+
+        *self.breeds.write().unwrap() = Some(breeds.clone());
+
+        Ok(breeds)
+    }
+
+    fn cat_stats_command(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        interaction::reply(
+            interaction_create,
+            CONTEXT.stats_page(interaction_create, 0),
+        )?;
+
+        Ok(())
+    }
+
+    fn cat_stats_page(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Ok(page) = Self::component_value(interaction_create, PluginStats::PAGE_COMPONENT)?
+            .parse::<usize>()
+        else {
+            return Err(PluginError::Missing("leaderboard page"));
+        };
+
+        interaction::update_message(
+            interaction_create,
+            CONTEXT.stats_page(interaction_create, page),
+        )?;
+
+        Ok(())
+    }
+
+    /// The stats embed with the provided leaderboard page and the page buttons, pages past the
+    /// end show the last page.
+    fn stats_page(
+        &self,
+        interaction_create: &InteractionCreate,
+        page: usize,
+    ) -> InteractionResponseData {
+        let locale = locale::of(interaction_create);
+
+        let stats = self.stats.read().unwrap();
+
+        let mut leaderboard = stats.most_cats_demanded.iter().collect::<Vec<_>>();
+
+        leaderboard.sort_by(|(_, cats), (_, other_cats)| other_cats.cmp(cats));
+
+        let pages = leaderboard.len().div_ceil(PluginStats::PAGE_SIZE).max(1);
+        let page = page.min(pages - 1);
+
+        let leaderboard_page = if leaderboard.is_empty() {
+            LOCALES.text(locale, "embed.cat-stats.leaderboard-empty")
+        } else {
+            leaderboard
+                .iter()
+                .enumerate()
+                .skip(page * PluginStats::PAGE_SIZE)
+                .take(PluginStats::PAGE_SIZE)
+                .map(|(rank, (user_id, cats))| {
+                    LOCALES.format(
+                        locale,
+                        "embed.cat-stats.leaderboard-entry",
+                        &[
+                            ("rank", &(rank + 1)),
+                            ("user", &format!("<@{user_id}>")),
+                            ("cats", cats),
+                        ],
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let counter = |key: &str, value: u32| EmbedField {
+            inline: true,
+            name: LOCALES.text(locale, key),
+            value: value.to_string(),
+        };
+
+        let mut embed = embed::requested_by(&LOCALES, interaction_create);
+
+        embed.title = Some(LOCALES.text(locale, "embed.cat-stats.title"));
+        embed.fields = vec![
+            counter(
+                "embed.cat-stats.total-cats-requested",
+                stats.total_cats_requested,
+            ),
+            counter("embed.cat-stats.cats-on-demand", stats.cats_on_demand),
+            counter(
+                "embed.cat-stats.cat-messages-detected",
+                stats.cat_messages_detected,
+            ),
+            counter("embed.cat-stats.automated-cats", stats.automated_cats),
+            EmbedField {
+                inline: false,
+                name: LOCALES.format(
+                    locale,
+                    "embed.cat-stats.leaderboard",
+                    &[("page", &(page + 1)), ("pages", &pages)],
+                ),
+                value: leaderboard_page,
+            },
+        ];
+
+        InteractionResponseData {
+            components: Some(vec![Self::page_buttons(
+                locale,
+                PluginStats::PAGE_COMPONENT,
+                page,
+                pages,
+            )]),
+            embeds: Some(vec![embed]),
+            ..Default::default()
+        }
+    }
+
+    /// Previous and next buttons, their custom IDs are the prefix followed by the target page.
+    fn page_buttons(locale: &str, prefix: &str, page: usize, pages: usize) -> Component {
+        let page_button = |key: &str, target: usize, disabled: bool| {
+            Self::button(
+                locale,
+                key,
+                format!("{prefix}{target}"),
+                ButtonStyle::Secondary,
+                disabled,
+            )
+        };
+
+        Component::ActionRow(ActionRow {
+            id: None,
+            components: vec![
+                page_button("button.previous", page.saturating_sub(1), page == 0),
+                page_button("button.next", page + 1, page + 1 >= pages),
+            ],
+        })
+    }
+
+    fn button(
+        locale: &str,
+        key: &str,
+        custom_id: String,
+        style: ButtonStyle,
+        disabled: bool,
+    ) -> Component {
+        Component::Button(Button {
+            id: None,
+            custom_id: Some(custom_id),
+            disabled,
+            emoji: None,
+            label: Some(LOCALES.text(locale, key)),
+            style,
+            url: None,
+            sku_id: None,
+        })
+    }
+
+    /// The custom ID of the message component without the provided prefix.
+    fn component_value<'a>(
+        interaction_create: &'a InteractionCreate,
+        prefix: &str,
+    ) -> Result<&'a str, PluginError> {
+        let Some(InteractionData::MessageComponent(component_data)) =
+            interaction_create.data.as_ref()
+        else {
+            return Err(PluginError::Missing("message component data"));
+        };
+
+        match component_data.custom_id.strip_prefix(prefix) {
+            Some(value) => Ok(value),
+            None => Err(PluginError::Missing("message component value")),
+        }
+    }
+
+    /// The stored stats, failing to load them is only logged so the counting starts over.
+    fn load_stats() -> PluginStats {
+        let stats = match storage::get(PluginStats::STORAGE_KEY) {
+            Ok(Some(stats)) => stats,
+            Ok(None) => return PluginStats::default(),
+            Err(err) => {
+                log(
+                    LogLevels::Warn,
+                    &format!("An error occured while loading the stats: {err}"),
+                );
+
+                return PluginStats::default();
             }
-            DiscordEvents::MessageCreate(message) => {
-                let message = Box::new(sonic_rs::from_slice::<MessageCreate>(&message).unwrap());
+        };
 
-                if message.0.content.to_lowercase().contains("cat") {
-                    return CONTEXT.cat_message(message);
-                }
-                Ok(())
+        match sonic_rs::from_slice::<PluginStats>(&stats) {
+            Ok(stats) => stats,
+            Err(err) => {
+                log(
+                    LogLevels::Warn,
+                    &format!("The stored stats were of the incorrect structure, error: {err}"),
+                );
+
+                PluginStats::default()
             }
-            _ => unimplemented!(),
         }
     }
 
-    fn scheduled_job(job: String) -> Result<(), String> {
-        match job.as_str() {
-            "automated_cat" => CONTEXT.automated_cat(),
-            &_ => unimplemented!(),
+    /// Updates the stats and stores them, failing to store them is only logged.
+    fn update_stats(&self, update: impl FnOnce(&mut PluginStats)) {
+        update(&mut self.stats.write().unwrap());
+
+        if let Err(err) = self.store_stats() {
+            log(
+                LogLevels::Warn,
+                &format!("An error occured while storing the stats: {err}"),
+            );
         }
     }
 
-    fn dependency_function(_function: String, _params: Vec<u8>) -> Result<Vec<u8>, String> {
-        todo!();
+    fn store_stats(&self) -> Result<(), String> {
+        let stats = match sonic_rs::to_vec(&*self.stats.read().unwrap()) {
+            Ok(stats) => stats,
+            Err(err) => {
+                return Err(format!(
+                    "An error occured while serializing the stats, error: {}",
+                    &err,
+                ));
+            }
+        };
+
+        storage::set(PluginStats::STORAGE_KEY, &stats, None)
     }
-}
 
-impl Plugin {
-    fn cat_command(&self, mut interaction: Box<InteractionCreate>) {
-        let mut discord_requests = vec![];
+    fn cat_message(&self, message: &MessageCreate) -> Result<(), PluginError> {
+        if message.author.bot || !self.mentions_cat(&message.content) {
+            return Ok(());
+        }
 
-        match interaction.data.as_mut().unwrap() {
-            InteractionData::ApplicationCommand(command_data) => {
-                command_data.options.reverse();
+        self.update_stats(|stats| stats.cat_messages_detected += 1);
 
-                let id = match command_data.options.pop() {
-                    Some(id) => match id.value {
-                        CommandOptionValue::String(id) => Some(id),
-                        _ => None,
-                    },
-                    None => None,
-                };
-
-                match self.http_client.request_cat(id) {
-                    Ok(mut cat_response) => {
-                        let interaction_response_data = InteractionResponseData {
-                            allowed_mentions: None,
-                            attachments: None,
-                            choices: None,
-                            components: None,
-                            content: Some(cat_response.pop().unwrap().url),
-                            custom_id: None,
-                            embeds: None,
-                            flags: None,
-                            title: None,
-                            tts: None,
-                            poll: None,
-                        };
-
-                        let interaction_response = InteractionResponse {
-                            kind: InteractionResponseType::ChannelMessageWithSource,
-                            data: Some(interaction_response_data),
-                        };
-
-                        discord_requests.push(DiscordRequests::InteractionCallback((
-                            interaction.id.get(),
-                            interaction.token.clone(),
-                            true,
-                            sonic_rs::to_vec(&interaction_response).unwrap(),
-                        )));
-                    }
-                    Err(err) => {
-                        // TODO: Use an embed
-
-                        //let embed = Self::create_error_embed(err);
-
-                        let interaction_response_data = InteractionResponseData {
-                            allowed_mentions: None,
-                            attachments: None,
-                            choices: None,
-                            components: None,
-                            content: Some(err),
-                            custom_id: None,
-                            embeds: None,
-                            flags: None,
-                            title: None,
-                            tts: None,
-                            poll: None,
-                        };
-
-                        let interaction_response = InteractionResponse {
-                            kind: InteractionResponseType::ChannelMessageWithSource,
-                            data: Some(interaction_response_data),
-                        };
-
-                        discord_requests.push(DiscordRequests::InteractionCallback((
-                            interaction.id.get(),
-                            interaction.token.clone(),
-                            true,
-                            sonic_rs::to_vec(&interaction_response).unwrap(),
-                        )));
-                    }
+        let (response_chance, cooldown) = {
+            let storred_settings = self.storred_settings.read().unwrap();
+
+            (
+                storred_settings.cat_message_response_chance,
+                storred_settings.cat_message_cooldown,
+            )
+        };
+
+        if !Self::roll(response_chance) {
+            return Ok(());
+        }
+
+        let now = UNIX_EPOCH.elapsed().unwrap_or_default().as_secs();
+
+        // The cooldown starts before the cat is requested, so a burst of messages gets one reply
+        match self
+            .cat_message_replies
+            .write()
+            .unwrap()
+            .entry(message.channel_id)
+        {
+            Entry::Occupied(mut last_reply) => {
+                if now.saturating_sub(*last_reply.get()) < cooldown {
+                    return Ok(());
                 }
+
+                last_reply.insert(now);
+            }
+            Entry::Vacant(last_reply) => {
+                last_reply.insert(now);
             }
-            _ => unreachable!(),
         }
 
-        for request in discord_requests {
-            let _ = discord_request(&request);
-        }
+        let Some(cat) = self.http_client.request_cat(&CatQuery::default())?.pop() else {
+            return Err(PluginError::Missing("cat"));
+        };
+
+        self.update_stats(|stats| stats.total_cats_requested += 1);
+
+        let contents = request::contents(
+            request::client()
+                .create_message(message.channel_id)
+                .components(&[Self::cat_components(DEFAULT_LOCALE, &cat, None)])
+                .embeds(&[Self::cat_embed(embed::base(), DEFAULT_LOCALE, cat)])
+                .reply(message.id),
+        )?;
+
+        discord_request(&Requests::CreateMessage((
+            message.channel_id.get(),
+            contents,
+        )))?;
+
+        Ok(())
     }
 
-    fn cat_message(&self, _message: Box<MessageCreate>) -> Result<(), String> {
-        todo!()
+    /// Whether the content contains one of the cat message words, "category" does not mention
+    /// cats.
+    fn mentions_cat(&self, content: &str) -> bool {
+        let cat_message_words = &self.storred_settings.read().unwrap().cat_message_words;
+
+        content
+            .split(|char: char| !char.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .any(|word| cat_message_words.contains(&word.to_lowercase()))
     }
 
-    fn automated_cat(&self) -> Result<(), String> {
-        todo!();
+    /// True with a chance of chance percent.
+    fn roll(chance: u8) -> bool {
+        let mut bytes = [0; 4];
+
+        get_insecure_random_bytes(&mut bytes);
+
+        u32::from_le_bytes(bytes) % 100 < u32::from(chance)
+    }
+
+    fn automated_cat(&self, job: &str) -> Result<(), PluginError> {
+        let Some(channel_id) = self
+            .storred_settings
+            .read()
+            .unwrap()
+            .automated_cats
+            .iter()
+            .find(|automated_cat| automated_cat.job == job)
+            .map(|automated_cat| automated_cat.channel)
+        else {
+            return Err(PluginError::Other(format!(
+                "The {job} job did not belong to a configured automated cat."
+            )));
+        };
+
+        let Some(cat) = self.http_client.request_cat(&CatQuery::default())?.pop() else {
+            return Err(PluginError::Missing("cat"));
+        };
+
+        self.update_stats(|stats| {
+            stats.total_cats_requested += 1;
+            stats.automated_cats += 1;
+        });
+
+        let contents = request::contents(
+            request::client()
+                .create_message(channel_id)
+                .components(&[Self::cat_components(DEFAULT_LOCALE, &cat, None)])
+                .embeds(&[Self::cat_embed(embed::base(), DEFAULT_LOCALE, cat)]),
+        )?;
+
+        discord_request(&Requests::CreateMessage((channel_id.get(), contents)))?;
+
+        Ok(())
     }
 
-    fn _create_error_embed<T: AsRef<str>>(_message: T) -> Embed {
-        todo!();
+    /// Adds the image, ID, dimensions and breed of the cat to the provided embed.
+    fn cat_embed(mut embed: Embed, locale: &str, cat: CatResponse) -> Embed {
+        let field = |key: &str, value: String| EmbedField {
+            inline: true,
+            name: LOCALES.text(locale, key),
+            value,
+        };
+
+        embed.title = Some(LOCALES.text(locale, "embed.cat.title"));
+
+        embed
+            .fields
+            .push(field("embed.cat.id", format!("`{}`", cat.id)));
+
+        if let (Some(width), Some(height)) = (cat.width, cat.height) {
+            embed
+                .fields
+                .push(field("embed.cat.dimensions", format!("{width} × {height}")));
+        }
+
+        if let Some(breed) = cat.breeds.first() {
+            embed
+                .fields
+                .push(field("embed.cat.breed", breed.name.clone()));
+        }
+
+        embed.image = Some(EmbedImage {
+            height: cat.height.map(u64::from),
+            proxy_url: None,
+            url: cat.url,
+            width: cat.width.map(u64::from),
+        });
+
+        embed
     }
 }
 
 // export! defines that the `Plugin` struct defined below is going to define
 // the exports of the `plugin` world, namely the `init` and `cleanup` function.
-export!(Plugin);
+plugin_sdk::export!(Plugin);
@@ -1,70 +1,96 @@
-use std::{
-    sync::{LazyLock, RwLock},
-    time::SystemTime,
-};
+use std::sync::{LazyLock, RwLock};
 
+use plugin_sdk::{
+    discord_bot::plugin::{
+        discord_types::Requests,
+        plugin_types::{
+            RegistrationsRequest, RegistrationsRequestDiscordEvents,
+            RegistrationsRequestInteractionCreate, RegistrationsResult, SupportedRegistrations,
+        },
+    },
+    embed,
+    error::PluginError,
+    exports::discord_bot::plugin::plugin_functions::{DiscordEvents, Guest},
+    forum, interaction,
+    locale::{self, Catalogs, DEFAULT_LOCALE, Locales},
+    modal::Modal,
+    registrations, request,
+    router::Router,
+};
+use schemars::{JsonSchema, schema_for};
 use serde::Deserialize;
-use twilight_http::{Client, request::TryIntoRequest};
 use twilight_model::{
     application::{
         command::{Command, CommandType},
-        interaction::{
-            InteractionContextType, InteractionData,
-            modal::{
-                ModalInteractionComponent, ModalInteractionStringSelect, ModalInteractionTextInput,
-            },
-        },
-    },
-    channel::{
-        Channel, ChannelType,
-        message::{
-            AllowedMentions, Component, Embed, EmojiReactionType, MentionType, MessageFlags,
-            component::{
-                Label, SelectMenu, SelectMenuOption, SelectMenuType, TextInput, TextInputStyle,
-            },
-            embed::EmbedFooter,
-        },
-        thread::ThreadsListing,
+        interaction::{InteractionContextType, InteractionData},
     },
+    channel::{Channel, thread::ThreadsListing},
     gateway::payload::incoming::InteractionCreate,
     guild::Permissions,
-    http::interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
     id::Id,
     oauth::ApplicationIntegrationType,
-    util::Timestamp,
 };
 use url::Url;
 
-wit_bindgen::generate!({ path: "../wit" });
-
-use crate::{
-    discord_bot::plugin::{
-        discord_types::{Contents, Requests},
-        host_functions::discord_request,
-        plugin_types::{
-            RegistrationsRequest, RegistrationsRequestDiscordEvents,
-            RegistrationsRequestInteractionCreate, SupportedRegistrations,
-        },
-    },
-    exports::discord_bot::plugin::plugin_functions::{DiscordEvents, Guest},
-};
-
 struct Plugin {
     settings: RwLock<PluginSettings>,
+    registrations: RwLock<Option<RegistrationsRequest>>,
 }
 
-#[derive(Deserialize)]
+/// Paperback extension requests as forum threads.
+#[derive(Deserialize, JsonSchema)]
 struct PluginSettings {
+    /// The ID of the forum channel extension request threads are created in.
     channel_id: u64,
+    /// The IDs of the forum tags applied to the extension request threads.
     tags: PluginSettingsTags,
+    /// Overrides of the localized strings, keyed by locale and then by string key.
+    #[serde(default)]
+    strings: Catalogs,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 struct PluginSettingsTags {
+    /// The ID of the content service forum tag.
     content_service: u64,
+    /// The ID of the tracker service forum tag.
     tracker_service: u64,
 }
 
+#[derive(Modal)]
+#[modal(id = "extension-request", title = "Extension Request")]
+struct ExtensionRequestModal {
+    #[modal(text_input, label = "Website URL", placeholder = "https://example.com")]
+    website_url: String,
+    #[modal(
+        string_select,
+        label = "Website Type",
+        min_values = 1,
+        max_values = 2,
+        option(
+            value = "content-service",
+            label = "Content Service",
+            description = "Websites which provide content services (e.g. MangaDex).",
+            emoji = "📚",
+            default
+        ),
+        option(
+            value = "tracker-service",
+            label = "Tracker Service",
+            description = "Websites which provide tracker services (e.g. AniList).",
+            emoji = "🗳️"
+        )
+    )]
+    website_type: Vec<String>,
+    #[modal(
+        text_input,
+        label = "Reason",
+        placeholder = "Why should this website be turned into an extension...",
+        paragraph
+    )]
+    reason: String,
+}
+
 static CONTEXT: LazyLock<Plugin> = LazyLock::new(|| Plugin {
     settings: RwLock::new(PluginSettings {
         channel_id: 0,
@@ -72,7 +98,18 @@ static CONTEXT: LazyLock<Plugin> = LazyLock::new(|| Plugin {
             content_service: 0,
             tracker_service: 0,
         },
+        strings: Catalogs::new(),
     }),
+    registrations: RwLock::new(None),
+});
+
+static LOCALES: LazyLock<Locales> = LazyLock::new(|| plugin_sdk::locales!("en-US", "nl"));
+
+static ROUTER: LazyLock<Router> = LazyLock::new(|| {
+    Router::new()
+        .locales(&LOCALES)
+        .command("request-extension", Plugin::request_extension)
+        .modal(ExtensionRequestModal::CUSTOM_ID, Plugin::extension_request)
 });
 
 impl Guest for Plugin {
@@ -88,63 +125,93 @@ impl Guest for Plugin {
             ));
         }
 
-        let settings = match sonic_rs::from_slice::<PluginSettings>(&settings) {
-            Ok(settings) => settings,
-            Err(err) => {
-                return Err(format!(
-                    "The provided settings were of the incorrect structure, error: {}",
-                    &err,
-                ));
+        let channel = Plugin::update_settings(&settings)?;
+
+        let registrations = Plugin::registrations(&channel);
+
+        *CONTEXT.registrations.write().unwrap() = Some(registrations.clone());
+
+        Ok(registrations)
+    }
+
+    fn settings_update(settings: Vec<u8>) -> Result<RegistrationsRequest, String> {
+        let channel = Plugin::update_settings(&settings)?;
+
+        let registrations = Plugin::registrations(&channel);
+
+        let previous_registrations = CONTEXT
+            .registrations
+            .write()
+            .unwrap()
+            .replace(registrations.clone());
+
+        Ok(registrations::diff(previous_registrations, registrations))
+    }
+
+    fn settings_schema() -> Vec<u8> {
+        sonic_rs::to_vec(&schema_for!(PluginSettings)).unwrap()
+    }
+
+    fn registrations_processed(registrations_result: RegistrationsResult) -> Result<(), String> {
+        registrations::log_result(registrations_result);
+
+        Ok(())
+    }
+
+    fn shutdown() -> Result<(), String> {
+        Ok(())
+    }
+
+    fn discord_event(event: DiscordEvents) -> Result<(), String> {
+        match event {
+            DiscordEvents::InteractionCreate(interaction_create) => {
+                ROUTER.handle(&interaction_create)
             }
-        };
+            _ => Err(String::from(
+                "The plugin received an event it did not register.",
+            )),
+        }
+    }
 
-        let get_channel_response =
-            discord_request(&Requests::GetChannel(settings.channel_id))?.unwrap();
+    fn scheduled_job(job: String, _payload: Option<Vec<u8>>) -> Result<(), String> {
+        Err(format!("Unknown scheduled job: {job}"))
+    }
 
-        let channel = match sonic_rs::from_slice::<Channel>(&get_channel_response) {
-            Ok(channel) => channel,
+    fn dependency_function(function: String, _params: Vec<u8>) -> Result<Vec<u8>, String> {
+        Err(format!(
+            "The {function} dependency function does not exist."
+        ))
+    }
+}
+
+impl Plugin {
+    fn update_settings(settings: &[u8]) -> Result<Channel, String> {
+        let settings = match sonic_rs::from_slice::<PluginSettings>(settings) {
+            Ok(settings) => settings,
             Err(err) => {
                 return Err(format!(
-                    "Something went wrong while deserializing the response from Discord, error {}",
-                    &err
+                    "The provided settings were of the incorrect structure, error: {}",
+                    &err,
                 ));
             }
         };
 
-        if channel.kind != ChannelType::GuildForum {
-            return Err(String::from(
-                "The provided requests channel needs to be of the forum type.",
-            ));
-        }
-
-        CONTEXT.settings.write().unwrap().channel_id = settings.channel_id;
+        let channel = forum::get_channel(
+            settings.channel_id,
+            &[
+                ("content service", settings.tags.content_service),
+                ("tracker service", settings.tags.tracker_service),
+            ],
+        )?;
 
-        if !channel
-            .available_tags
-            .as_ref()
-            .unwrap()
-            .iter()
-            .any(|forum_tag| forum_tag.id == settings.tags.content_service)
-        {
-            return Err(String::from(
-                "The provided content service tag ID was not available in the provided forum channel.",
-            ));
-        }
+        LOCALES.set_overrides(settings.strings.clone());
 
-        if !channel
-            .available_tags
-            .as_ref()
-            .unwrap()
-            .iter()
-            .any(|forum_tag| forum_tag.id == settings.tags.tracker_service)
-        {
-            return Err(String::from(
-                "The provided tracker service tag ID was not available in the provided forum channel.",
-            ));
-        }
+        *CONTEXT.settings.write().unwrap() = settings;
 
-        CONTEXT.settings.write().unwrap().tags = settings.tags;
+        Ok(channel)
+    }
 
+    fn registrations(channel: &Channel) -> RegistrationsRequest {
         let commands = vec![
             sonic_rs::to_vec(&Command {
                 application_id: None,
@@ -152,14 +219,15 @@ impl Guest for Plugin {
                 default_member_permissions: Some(Permissions::SEND_MESSAGES),
                 #[allow(deprecated)]
                 dm_permission: None,
-                description: String::from("Request a Paperback extension"),
-                description_localizations: None,
+                description: LOCALES.text(DEFAULT_LOCALE, "command.request-extension.description"),
+                description_localizations: LOCALES
+                    .localizations("command.request-extension.description"),
                 guild_id: Some(channel.guild_id.unwrap()),
                 id: None,
                 integration_types: Some(vec![ApplicationIntegrationType::GuildInstall]),
                 kind: CommandType::ChatInput,
                 name: String::from("request-extension"),
-                name_localizations: None,
+                name_localizations: LOCALES.localizations("command.request-extension.name"),
                 nsfw: Some(false),
                 options: vec![],
                 version: Id::new(1),
@@ -167,215 +235,41 @@ impl Guest for Plugin {
             .unwrap(),
         ];
 
-        Ok(RegistrationsRequest {
+        RegistrationsRequest {
             discord_events: Some(RegistrationsRequestDiscordEvents {
                 interaction_create: Some(RegistrationsRequestInteractionCreate {
                     application_commands: Some(commands),
                     message_components: None,
-                    modals: Some(vec![String::from("extension-request")]),
+                    modals: Some(vec![String::from(ExtensionRequestModal::CUSTOM_ID)]),
                 }),
-                message_create: false,
-                thread_create: false,
-                thread_delete: false,
-                thread_list_sync: false,
-                thread_member_update: false,
-                thread_members_update: false,
-                thread_update: false,
+                ..registrations::discord_events()
             }),
             scheduled_jobs: None,
             dependency_functions: None,
-        })
-    }
-
-    fn shutdown() -> Result<(), String> {
-        Ok(())
-    }
-
-    fn discord_event(event: DiscordEvents) -> Result<(), String> {
-        match event {
-            DiscordEvents::InteractionCreate(interaction_create) => {
-                let interaction_create =
-                    sonic_rs::from_slice::<InteractionCreate>(&interaction_create).unwrap();
-
-                match &interaction_create.data {
-                    Some(InteractionData::ApplicationCommand(command_data)) => {
-                        match command_data.name.as_str() {
-                            "request-extension" => Plugin::request_extension(&interaction_create),
-                            &_ => unimplemented!(),
-                        }
-                    }
-                    Some(InteractionData::ModalSubmit(modal_interaction_data)) => {
-                        match modal_interaction_data.custom_id.as_str() {
-                            "extension-request" => Plugin::extension_request(&interaction_create),
-                            &_ => unimplemented!(),
-                        }
-                    }
-                    _ => unimplemented!(),
-                }
-            }
-            _ => unimplemented!(),
         }
     }
 
-    fn scheduled_job(_job: String) -> Result<(), String> {
-        unimplemented!();
-    }
-
-    fn dependency_function(_function: String, _params: Vec<u8>) -> Result<Vec<u8>, String> {
-        unimplemented!();
-    }
-}
-
-impl Plugin {
-    #[allow(clippy::too_many_lines)]
-    fn request_extension(interaction_create: &InteractionCreate) -> Result<(), String> {
-        let modal = InteractionResponse {
-            kind: InteractionResponseType::Modal,
-            data: Some(InteractionResponseData {
-                allowed_mentions: Some(AllowedMentions {
-                    parse: vec![MentionType::Users],
-                    replied_user: true,
-                    roles: vec![],
-                    users: vec![],
-                }),
-                attachments: None,
-                choices: None,
-                components: Some(vec![
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Website URL"),
-                        description: None,
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("website-url"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("https://example.com")),
-                            required: Some(true),
-                            style: TextInputStyle::Short,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Website Type"),
-                        description: None,
-                        component: Box::new(Component::SelectMenu(SelectMenu {
-                            id: None,
-                            channel_types: None,
-                            custom_id: String::from("website-type"),
-                            default_values: None,
-                            disabled: false,
-                            kind: SelectMenuType::Text,
-                            max_values: Some(2),
-                            min_values: Some(1),
-                            options: Some(vec![
-                                SelectMenuOption {
-                                    default: true,
-                                    description: Some(String::from(
-                                        "Websites which provide content services (e.g. MangaDex).",
-                                    )),
-                                    emoji: Some(EmojiReactionType::Unicode {
-                                        name: String::from("📚"),
-                                    }),
-                                    label: String::from("Content Service"),
-                                    value: String::from("content-service"),
-                                },
-                                SelectMenuOption {
-                                    default: false,
-                                    description: Some(String::from(
-                                        "Websites which provide tracker services (e.g. AniList).",
-                                    )),
-                                    emoji: Some(EmojiReactionType::Unicode {
-                                        name: String::from("🗳️"),
-                                    }),
-                                    label: String::from("Tracker Service"),
-                                    value: String::from("tracker-service"),
-                                },
-                            ]),
-                            placeholder: None,
-                            required: Some(true),
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Reason"),
-                        description: None,
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("reason"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from(
-                                "Why should this website be turned into an extension...",
-                            )),
-                            required: Some(true),
-                            style: TextInputStyle::Paragraph,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                ]),
-                content: None,
-                custom_id: Some(String::from("extension-request")),
-                embeds: None,
-                flags: None,
-                title: Some(String::from("Extension Request")),
-                tts: None,
-                poll: None,
-            }),
-        };
-
-        discord_request(
-            &discord_bot::plugin::discord_types::Requests::InteractionCallback((
-                interaction_create.id.get(),
-                interaction_create.token.clone(),
-                true,
-                sonic_rs::to_vec(&modal).unwrap(),
-            )),
+    fn request_extension(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        interaction::modal(
+            interaction_create,
+            ExtensionRequestModal::modal(&LOCALES, locale::of(interaction_create)),
         )?;
 
         Ok(())
     }
 
-    fn extension_request(interaction_create: &InteractionCreate) -> Result<(), String> {
-        let (
-            extension_request_website_url,
-            extension_request_website_type,
-            extension_request_reason,
-        ) = Self::parse_modal(interaction_create);
-
-        discord_request(&Requests::InteractionCallback((
-            interaction_create.id.get(),
-            interaction_create.token.clone(),
-            true,
-            sonic_rs::to_vec(&InteractionResponse {
-                kind: InteractionResponseType::DeferredChannelMessageWithSource,
-                data: Some(InteractionResponseData {
-                    allowed_mentions: None,
-                    attachments: None,
-                    choices: None,
-                    components: None,
-                    content: None,
-                    custom_id: None,
-                    embeds: None,
-                    flags: Some(MessageFlags::EPHEMERAL),
-                    title: None,
-                    tts: None,
-                    poll: None,
-                }),
-            })
-            .unwrap(),
-        )))?;
+    fn extension_request(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Some(InteractionData::ModalSubmit(modal_interaction_data)) =
+            interaction_create.data.as_ref()
+        else {
+            return Err(PluginError::Missing("modal submission data"));
+        };
 
-        let client = Client::builder().build();
+        let modal = ExtensionRequestModal::parse(modal_interaction_data)?;
 
-        let Some(url) =
-            Self::validate_url(interaction_create, &extension_request_website_url.value)?
-        else {
+        interaction::defer_ephemeral(interaction_create)?;
+
+        let Some(url) = Self::validate_url(interaction_create, &modal.website_url)? else {
             return Ok(());
         };
 
@@ -385,7 +279,7 @@ impl Plugin {
             extension_request_title += url.path();
         }
 
-        if Self::forum_thread_existance(&client, interaction_create, &extension_request_title)? {
+        if Self::forum_thread_existance(interaction_create, &extension_request_title)? {
             return Ok(());
         }
 
@@ -393,217 +287,65 @@ impl Plugin {
             interaction_create,
             &extension_request_title,
             &url,
-            extension_request_website_type,
-            extension_request_reason,
+            &modal.website_type,
+            &modal.reason,
         )?;
 
-        let mut embed = Self::base_embed(interaction_create);
+        let locale = locale::of(interaction_create);
 
-        embed.title = Some(String::from("Created Extension Request"));
+        let mut embed = embed::requested_by(&LOCALES, interaction_create);
 
-        embed.description = Some(format!(
-            "An extension request has been created: <#{}>",
-            extension_request_thread.id
-        ));
+        embed.title = Some(LOCALES.text(locale, "embed.extension-request-created.title"));
 
-        let response_message_request = match client
-            .create_message(interaction_create.channel.as_ref().unwrap().id)
-            .flags(MessageFlags::EPHEMERAL)
-            .embeds(&[embed])
-            .try_into_request()
-        {
-            Ok(response_message_request) => response_message_request,
-            Err(err) => {
-                return Err(format!(
-                    "An error occured while building the response message request: {err}"
-                ));
-            }
-        };
+        embed.description = Some(LOCALES.format(
+            locale,
+            "embed.extension-request-created.description",
+            &[("thread", &format!("<#{}>", extension_request_thread.id))],
+        ));
 
-        discord_request(
-            &discord_bot::plugin::discord_types::Requests::UpdateInteractionOriginal((
-                interaction_create.application_id.get(),
-                interaction_create.token.clone(),
-                response_message_request.body().unwrap().to_owned(),
-            )),
-        )?;
+        interaction::update_original_embeds(interaction_create, &[embed])?;
 
         Ok(())
     }
 
-    fn parse_modal(
+    fn forum_thread_existance(
         interaction_create: &InteractionCreate,
-    ) -> (
-        &ModalInteractionTextInput,
-        &ModalInteractionStringSelect,
-        &ModalInteractionTextInput,
-    ) {
-        let InteractionData::ModalSubmit(modal_interaction_data) =
-            interaction_create.data.as_ref().unwrap()
-        else {
-            unreachable!()
-        };
-
-        let ModalInteractionComponent::TextInput(extension_request_website_url) = ({
-            let ModalInteractionComponent::Label(extension_request_website_url_label) =
-                &modal_interaction_data.components[0]
-            else {
-                unreachable!()
-            };
-
-            extension_request_website_url_label.component.as_ref()
-        }) else {
-            unreachable!()
-        };
-
-        let ModalInteractionComponent::StringSelect(extension_request_website_type) = ({
-            let ModalInteractionComponent::Label(extension_request_website_type_label) =
-                &modal_interaction_data.components[1]
-            else {
-                unreachable!()
-            };
-
-            extension_request_website_type_label.component.as_ref()
-        }) else {
-            unreachable!()
-        };
-
-        let ModalInteractionComponent::TextInput(extension_request_reason) = ({
-            let ModalInteractionComponent::Label(extension_request_reason_label) =
-                &modal_interaction_data.components[2]
-            else {
-                unreachable!()
-            };
-
-            extension_request_reason_label.component.as_ref()
-        }) else {
-            unreachable!()
+        extension_request_title: &str,
+    ) -> Result<bool, PluginError> {
+        let Some(guild_id) = interaction_create.guild_id else {
+            return Err(PluginError::Missing("guild ID"));
         };
 
-        (
-            extension_request_website_url,
-            extension_request_website_type,
-            extension_request_reason,
-        )
-    }
-
-    fn base_embed(interaction_create: &InteractionCreate) -> Embed {
-        Embed {
-            author: None,
-            color: Some(0x00E7_2323),
-            description: None,
-            fields: vec![],
-            footer: Some(EmbedFooter {
-                icon_url: interaction_create
-                    .member
-                    .as_ref()
-                    .unwrap()
-                    .user
-                    .as_ref()
-                    .unwrap()
-                    .avatar
-                    .map(|avatar| {
-                        format!(
-                            "https://cdn.discordapp.com/avatars/{}/{}.webp",
-                            interaction_create
-                                .member
-                                .as_ref()
-                                .unwrap()
-                                .user
-                                .as_ref()
-                                .unwrap()
-                                .id
-                                .get(),
-                            avatar
-                        )
-                    }),
-                proxy_icon_url: None,
-                text: format!(
-                    "Requested by {}",
-                    interaction_create
-                        .member
-                        .as_ref()
-                        .unwrap()
-                        .user
-                        .as_ref()
-                        .unwrap()
-                        .name
-                        .clone()
-                ),
-            }),
-            image: None,
-            kind: String::from("rich"),
-            provider: None,
-            thumbnail: None,
-            timestamp: Some(
-                Timestamp::from_secs(
-                    SystemTime::now()
-                        .elapsed()
-                        .unwrap_or_default()
-                        .as_secs()
-                        .try_into()
-                        .unwrap_or_default(),
-                )
-                .unwrap_or(Timestamp::from_secs(0).unwrap()),
-            ),
-            title: None,
-            url: None,
-            video: None,
-        }
-    }
+        let active_threads = request::fetch::<ThreadsListing>(
+            &Requests::GetActiveThreads(guild_id.get()),
+            "active threads",
+        )?;
 
-    fn forum_thread_existance(
-        client: &Client,
-        interaction_create: &InteractionCreate,
-        extension_request_title: &str,
-    ) -> Result<bool, String> {
-        let get_active_threads_response = discord_request(&Requests::GetActiveThreads(
-            interaction_create.guild_id.unwrap().get(),
-        ))?
-        .unwrap();
-
-        let active_threads =
-            match sonic_rs::from_slice::<ThreadsListing>(&get_active_threads_response) {
-                Ok(active_threads) => active_threads,
-                Err(err) => return Err(err.to_string()),
-            };
+        let channel_id = CONTEXT.settings.read().unwrap().channel_id;
 
         if let Some(existing_extension_request_thread) =
             active_threads.threads.into_iter().find(|t| {
-                t.parent_id.unwrap().get() == CONTEXT.settings.read().unwrap().channel_id
+                t.parent_id
+                    .is_some_and(|parent_id| parent_id.get() == channel_id)
                     && t.name.as_ref().unwrap_or(&String::new()) == extension_request_title
             })
         {
-            let mut embed = Self::base_embed(interaction_create);
+            let locale = locale::of(interaction_create);
 
-            embed.title = Some(String::from("Extension Request Already Exists"));
+            let mut embed = embed::requested_by(&LOCALES, interaction_create);
 
-            embed.description = Some(format!(
-                "An extension request for this website already exists: <#{}>",
-                &existing_extension_request_thread.id
+            embed.title = Some(LOCALES.text(locale, "embed.extension-request-exists.title"));
+
+            embed.description = Some(LOCALES.format(
+                locale,
+                "embed.extension-request-exists.description",
+                &[(
+                    "thread",
+                    &format!("<#{}>", existing_extension_request_thread.id),
+                )],
             ));
 
-            let response_message_request = match client
-                .create_message(interaction_create.channel.as_ref().unwrap().id)
-                .flags(MessageFlags::EPHEMERAL)
-                .embeds(&[embed])
-                .try_into_request()
-            {
-                Ok(response_message_request) => response_message_request,
-                Err(err) => {
-                    return Err(format!(
-                        "An error occured while building the response message request: {err}"
-                    ));
-                }
-            };
-
-            discord_request(
-                &discord_bot::plugin::discord_types::Requests::UpdateInteractionOriginal((
-                    interaction_create.application_id.get(),
-                    interaction_create.token.clone(),
-                    response_message_request.body().unwrap().to_owned(),
-                )),
-            )?;
+            interaction::update_original_embeds(interaction_create, &[embed])?;
 
             return Ok(true);
         }
@@ -615,17 +357,17 @@ impl Plugin {
         interaction_create: &InteractionCreate,
         extension_request_title: &str,
         url: &Url,
-        extension_request_website_type: &ModalInteractionStringSelect,
-        extension_request_reason: &ModalInteractionTextInput,
-    ) -> Result<Channel, String> {
-        let mut embed = Self::base_embed(interaction_create);
+        extension_request_website_type: &[String],
+        extension_request_reason: &str,
+    ) -> Result<Channel, PluginError> {
+        let mut embed = embed::requested_by(&LOCALES, interaction_create);
 
         embed.title = Some(extension_request_title.to_string());
         embed.url = Some(url.to_string());
 
         let mut extension_request_tags = vec![];
 
-        for website_type in &extension_request_website_type.values {
+        for website_type in extension_request_website_type {
             match website_type.as_str() {
                 "content-service" => extension_request_tags.push(Id::new(
                     CONTEXT.settings.read().unwrap().tags.content_service,
@@ -633,100 +375,73 @@ impl Plugin {
                 "tracker-service" => extension_request_tags.push(Id::new(
                     CONTEXT.settings.read().unwrap().tags.tracker_service,
                 )),
-                &_ => unreachable!(),
+                website_type => {
+                    return Err(PluginError::Other(LOCALES.format(
+                        locale::of(interaction_create),
+                        "error.website-type",
+                        &[("website_type", &website_type)],
+                    )));
+                }
             }
         }
 
-        embed.description = Some(format!("**Reason**\n{}", &extension_request_reason.value));
-
-        let client = Client::builder().build();
-
-        let forum_thread_request = match client
-            .create_forum_thread(
-                Id::new(CONTEXT.settings.read().unwrap().channel_id),
-                extension_request_title,
-            )
-            .applied_tags(&extension_request_tags)
-            .message()
-            .embeds(&[embed])
-            .try_into_request()
-        {
-            Ok(forum_thread_request) => forum_thread_request,
-            Err(err) => {
-                return Err(format!(
-                    "An error occured while making a forum thread request: {err}"
-                ));
-            }
-        };
+        embed.description = Some(LOCALES.format(
+            locale::of_guild(interaction_create),
+            "thread.reason",
+            &[("reason", &extension_request_reason)],
+        ));
 
-        let create_forum_thread_response = discord_request(&Requests::CreateForumThread((
-            CONTEXT.settings.read().unwrap().channel_id,
-            Contents::Json(forum_thread_request.body().unwrap().to_owned()),
-        )))?
-        .unwrap();
+        let content = request::contents(
+            request::client()
+                .create_forum_thread(
+                    Id::new(CONTEXT.settings.read().unwrap().channel_id),
+                    extension_request_title,
+                )
+                .applied_tags(&extension_request_tags)
+                .message()
+                .embeds(&[embed]),
+        )?;
 
-        match sonic_rs::from_slice::<Channel>(&create_forum_thread_response) {
-            Ok(extension_request_thread) => Ok(extension_request_thread),
-            Err(err) => Err(err.to_string()),
-        }
+        request::fetch(
+            &Requests::CreateForumThread((CONTEXT.settings.read().unwrap().channel_id, content)),
+            "created forum thread",
+        )
     }
 
     fn validate_url(
         interaction_create: &InteractionCreate,
         url_str: &str,
-    ) -> Result<Option<Url>, String> {
+    ) -> Result<Option<Url>, PluginError> {
+        let locale = locale::of(interaction_create);
+
         let embed = match Url::parse(url_str) {
             Ok(url) => {
                 if url.scheme() == "https" {
                     return Ok(Some(url));
                 }
 
-                let mut embed = Self::base_embed(interaction_create);
+                let mut embed = embed::requested_by(&LOCALES, interaction_create);
 
-                embed.title = Some(String::from("URL Error"));
-                embed.description = Some(String::from(
-                    "The provided URL did not use the HTTPS origin. URLs should always start with \"https://\".",
-                ));
+                embed.title = Some(LOCALES.text(locale, "embed.url-error.title"));
+                embed.description = Some(LOCALES.text(locale, "embed.url-error.not-https"));
 
                 embed
             }
             Err(err) => {
-                let mut embed = Self::base_embed(interaction_create);
+                let mut embed = embed::requested_by(&LOCALES, interaction_create);
 
-                embed.title = Some(String::from("URL Error"));
+                embed.title = Some(LOCALES.text(locale, "embed.url-error.title"));
                 embed.description =
-                    Some(format!("The provided URL was not valid, error: {}", &err));
+                    Some(LOCALES.format(locale, "embed.url-error.invalid", &[("error", &err)]));
 
                 embed
             }
         };
 
-        let client = Client::builder().build();
-
-        let response_message_request = match client
-            .create_message(interaction_create.channel.as_ref().unwrap().id)
-            .flags(MessageFlags::EPHEMERAL)
-            .embeds(&[embed])
-            .try_into_request()
-        {
-            Ok(response_message_request) => response_message_request,
-            Err(err) => {
-                return Err(format!(
-                    "An error occured while building the response message request: {err}"
-                ));
-            }
-        };
-
-        discord_request(
-            &discord_bot::plugin::discord_types::Requests::UpdateInteractionOriginal((
-                interaction_create.application_id.get(),
-                interaction_create.token.clone(),
-                response_message_request.body().unwrap().to_owned(),
-            )),
-        )?;
+        interaction::update_original_embeds(interaction_create, &[embed])?;
 
         Ok(None)
     }
 }
 
-export!(Plugin);
+plugin_sdk::export!(Plugin);
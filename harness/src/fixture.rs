@@ -0,0 +1,390 @@
+//! Gateway event fixtures, JSONL files of which every line is an event, the canned responses to
+//! the Discord requests it causes and the requests the plugin is expected to make:
+//!
+//! ```json
+//! {"name": "spam", "event": {"message-create": {...}}, "responses": [{"ok": {...}}, {"err": {"status": 403, "code": 50013, "message": "Missing Permissions"}}], "requests": [{"delete-message": [201, 300]}], "error": null}
+//! ```
+//!
+//! - `event` is an `events` variant named as in the WIT, with the gateway event JSON as value.
+//! - `responses` are queued on the [`FakeHost`](crate::FakeHost) before the event is sent.
+//! - `requests` are `requests` variants named as in the WIT, tuples are arrays and JSON bodies are
+//!   inlined. Multipart forms are written as `{"form": null}`, their boundary is random.
+//! - `error` is the error discord-event is expected to return, none when it should succeed.
+//!
+//! The string `"*"` matches any value in `requests`, which is how timestamps are left out.
+//!
+//! Lines are replayed in order against the same plugin instance. Setting the `RECORD_FIXTURES`
+//! environment variable rewrites `requests` and `error` with what the plugin actually did instead
+//! of comparing them, wildcards have to be put back by hand afterwards.
+
+use std::{env, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{Contents, DiscordError, Events, PluginInstance, Requests};
+
+/// A line of a fixture file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Fixture {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub event: Value,
+    #[serde(default)]
+    pub responses: Vec<Response>,
+    #[serde(default)]
+    pub requests: Vec<Value>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// A canned response to a Discord request.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Response {
+    /// The response JSON, null for requests without a response body.
+    Ok(Value),
+    Err {
+        status: Option<u16>,
+        code: Option<u32>,
+        message: String,
+        #[serde(default)]
+        retry_after: Option<f64>,
+    },
+}
+
+impl Fixture {
+    /// Loads `fixtures/<name>.jsonl` from the harness crate, empty lines are skipped.
+    pub fn load(name: &str) -> Vec<Self> {
+        let path = path(name);
+
+        let fixtures = match fs::read_to_string(&path) {
+            Ok(fixtures) => fixtures,
+            Err(err) => panic!("The {} fixtures could not be read: {err}", path.display()),
+        };
+
+        fixtures
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| match serde_json::from_str(line) {
+                Ok(fixture) => fixture,
+                Err(err) => panic!(
+                    "Line {} of the {} fixtures was of the incorrect structure: {err}",
+                    index + 1,
+                    path.display()
+                ),
+            })
+            .collect()
+    }
+
+    /// A short description of the fixture for failure messages.
+    fn describe(&self, index: usize) -> String {
+        match &self.name {
+            Some(name) => format!("fixture {} ({name})", index + 1),
+            None => format!("fixture {}", index + 1),
+        }
+    }
+}
+
+impl PluginInstance {
+    /// Replays `fixtures/<name>.jsonl` and panics with a diff of every fixture which did not
+    /// match, or records the fixtures when `RECORD_FIXTURES` is set.
+    pub fn replay(&mut self, name: &str) {
+        let record = env::var_os("RECORD_FIXTURES").is_some();
+
+        let mut fixtures = Fixture::load(name);
+        let mut failures = vec![];
+
+        for (index, fixture) in fixtures.iter_mut().enumerate() {
+            let event = event(&fixture.event);
+
+            for response in &fixture.responses {
+                self.host_mut().responses.push_back(response.clone().into());
+            }
+
+            let error = self.discord_event(&event).err();
+
+            let requests = self.take_requests().iter().map(request).collect::<Vec<_>>();
+
+            let unused_responses = std::mem::take(&mut self.host_mut().responses).len();
+
+            if record {
+                fixture.requests = requests;
+                fixture.error = error;
+
+                continue;
+            }
+
+            let mut differences = diff(&fixture.requests, &requests);
+
+            if fixture.error != error {
+                differences.push(format!("expected error {:?}, got {error:?}", fixture.error));
+            }
+
+            if unused_responses != 0 {
+                differences.push(format!("{unused_responses} responses were not used"));
+            }
+
+            if !differences.is_empty() {
+                failures.push(format!(
+                    "{}:\n  {}",
+                    fixture.describe(index),
+                    differences.join("\n  ")
+                ));
+            }
+        }
+
+        if record {
+            let lines = fixtures
+                .iter()
+                .map(|fixture| serde_json::to_string(fixture).unwrap() + "\n")
+                .collect::<String>();
+
+            fs::write(path(name), lines).unwrap();
+
+            return;
+        }
+
+        if !failures.is_empty() {
+            panic!(
+                "The {name} fixtures did not match, rerun with RECORD_FIXTURES=1 to record them.\n\n{}",
+                failures.join("\n\n")
+            );
+        }
+    }
+}
+
+impl From<Response> for Result<Option<Vec<u8>>, DiscordError> {
+    fn from(response: Response) -> Self {
+        match response {
+            Response::Ok(Value::Null) => Ok(None),
+            Response::Ok(body) => Ok(Some(serde_json::to_vec(&body).unwrap())),
+            Response::Err {
+                status,
+                code,
+                message,
+                retry_after,
+            } => Err(DiscordError {
+                status,
+                code,
+                message,
+                retry_after,
+            }),
+        }
+    }
+}
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(format!("{name}.jsonl"))
+}
+
+fn event(event: &Value) -> Events {
+    let Some((kind, data)) = event.as_object().and_then(|event| event.iter().next()) else {
+        panic!("The fixture event {event} was not an object with the event as its only key");
+    };
+
+    let data = serde_json::to_vec(data).unwrap();
+
+    match kind.as_str() {
+        "guild-member-add" => Events::GuildMemberAdd(data),
+        "guild-member-remove" => Events::GuildMemberRemove(data),
+        "guild-member-update" => Events::GuildMemberUpdate(data),
+        "interaction-create" => Events::InteractionCreate(data),
+        "message-create" => Events::MessageCreate(data),
+        "message-delete" => Events::MessageDelete(data),
+        "message-delete-bulk" => Events::MessageDeleteBulk(data),
+        "message-reaction-add" => Events::MessageReactionAdd(data),
+        "message-reaction-remove" => Events::MessageReactionRemove(data),
+        "message-update" => Events::MessageUpdate(data),
+        "thread-create" => Events::ThreadCreate(data),
+        "thread-delete" => Events::ThreadDelete(data),
+        "thread-list-sync" => Events::ThreadListSync(data),
+        "thread-member-update" => Events::ThreadMemberUpdate(data),
+        "thread-members-update" => Events::ThreadMembersUpdate(data),
+        "thread-update" => Events::ThreadUpdate(data),
+        kind => panic!("The fixture event {kind} is not an events variant"),
+    }
+}
+
+/// The fixture representation of a request.
+pub fn request(request: &Requests) -> Value {
+    let (kind, params) = match request {
+        Requests::RequestGuildMembers((guild_id, body)) => {
+            ("request-guild-members", json!([guild_id, self::body(body)]))
+        }
+        Requests::RequestSoundboardSounds(guild_ids) => {
+            ("request-soundboard-sounds", json!(guild_ids))
+        }
+        Requests::UpdateVoiceState((guild_id, body)) => {
+            ("update-voice-state", json!([guild_id, self::body(body)]))
+        }
+        Requests::UpdatePresence(body) => ("update-presence", self::body(body)),
+        Requests::AddGuildMemberRole((guild_id, user_id, role_id)) => {
+            ("add-guild-member-role", json!([guild_id, user_id, role_id]))
+        }
+        Requests::AddThreadMember((channel_id, user_id)) => {
+            ("add-thread-member", json!([channel_id, user_id]))
+        }
+        Requests::CreateBan((guild_id, user_id, body)) => {
+            ("create-ban", json!([guild_id, user_id, self::body(body)]))
+        }
+        Requests::CreateFollowup((application_id, token, body)) => (
+            "create-followup",
+            json!([application_id, token, self::body(body)]),
+        ),
+        Requests::CreateForumThread((channel_id, contents)) => (
+            "create-forum-thread",
+            json!([channel_id, self::contents(contents)]),
+        ),
+        Requests::CreateMessage((channel_id, contents)) => (
+            "create-message",
+            json!([channel_id, self::contents(contents)]),
+        ),
+        Requests::CreateReaction((channel_id, message_id, emoji)) => {
+            ("create-reaction", json!([channel_id, message_id, emoji]))
+        }
+        Requests::CreateThread((channel_id, body)) => {
+            ("create-thread", json!([channel_id, self::body(body)]))
+        }
+        Requests::CreateThreadFromMessage((channel_id, message_id, body)) => (
+            "create-thread-from-message",
+            json!([channel_id, message_id, self::body(body)]),
+        ),
+        Requests::DeleteMessage((channel_id, message_id)) => {
+            ("delete-message", json!([channel_id, message_id]))
+        }
+        Requests::DeleteMessages((channel_id, message_ids)) => {
+            ("delete-messages", json!([channel_id, message_ids]))
+        }
+        Requests::DeleteReaction((channel_id, message_id, emoji, user_id)) => (
+            "delete-reaction",
+            json!([channel_id, message_id, emoji, user_id]),
+        ),
+        Requests::GetActiveThreads(guild_id) => ("get-active-threads", json!(guild_id)),
+        Requests::GetChannel(channel_id) => ("get-channel", json!(channel_id)),
+        Requests::GetChannelMessages((after, around, before, channel_id, limit)) => (
+            "get-channel-messages",
+            json!([after, around, before, channel_id, limit]),
+        ),
+        Requests::GetJoinedPrivateArchivedThreads((before, channel_id, limit)) => (
+            "get-joined-private-archived-threads",
+            json!([before, channel_id, limit]),
+        ),
+        Requests::GetPrivateArchivedThreads((before, channel_id, limit)) => (
+            "get-private-archived-threads",
+            json!([before, channel_id, limit]),
+        ),
+        Requests::GetPublicArchivedThreads((before, channel_id, limit)) => (
+            "get-public-archived-threads",
+            json!([before, channel_id, limit]),
+        ),
+        Requests::GetThreadMember((channel_id, user_id)) => {
+            ("get-thread-member", json!([channel_id, user_id]))
+        }
+        Requests::GetThreadMembers((after, channel_id, limit, with_member)) => (
+            "get-thread-members",
+            json!([after, channel_id, limit, with_member]),
+        ),
+        Requests::InteractionCallback((interaction_id, token, with_response, body)) => (
+            "interaction-callback",
+            json!([interaction_id, token, with_response, self::body(body)]),
+        ),
+        Requests::JoinThread(channel_id) => ("join-thread", json!(channel_id)),
+        Requests::LeaveThread(channel_id) => ("leave-thread", json!(channel_id)),
+        Requests::RemoveGuildMember((guild_id, user_id)) => {
+            ("remove-guild-member", json!([guild_id, user_id]))
+        }
+        Requests::RemoveGuildMemberRole((guild_id, user_id, role_id)) => (
+            "remove-guild-member-role",
+            json!([guild_id, user_id, role_id]),
+        ),
+        Requests::RemoveThreadMember((channel_id, user_id)) => {
+            ("remove-thread-member", json!([channel_id, user_id]))
+        }
+        Requests::UpdateChannel((channel_id, body)) => {
+            ("update-channel", json!([channel_id, self::body(body)]))
+        }
+        Requests::UpdateMember((guild_id, user_id, body)) => (
+            "update-member",
+            json!([guild_id, user_id, self::body(body)]),
+        ),
+        Requests::UpdateMessage((channel_id, message_id, body)) => (
+            "update-message",
+            json!([channel_id, message_id, self::body(body)]),
+        ),
+        Requests::UpdateInteractionOriginal((application_id, token, body)) => (
+            "update-interaction-original",
+            json!([application_id, token, self::body(body)]),
+        ),
+    };
+
+    json!({ kind: params })
+}
+
+/// JSON bodies are inlined, empty bodies are null and anything else is kept as a string.
+fn body(body: &[u8]) -> Value {
+    if body.is_empty() {
+        return Value::Null;
+    }
+
+    serde_json::from_slice(body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()))
+}
+
+fn contents(contents: &Contents) -> Value {
+    match contents {
+        Contents::Json(body) => json!({ "json": self::body(body) }),
+        Contents::Form(_) => json!({ "form": null }),
+    }
+}
+
+/// Compares the requests in order and describes every difference.
+fn diff(expected: &[Value], actual: &[Value]) -> Vec<String> {
+    let mut differences = vec![];
+
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if matches(expected, actual) => {}
+            (Some(expected), Some(actual)) => differences.push(format!(
+                "request {} differed\n    expected: {expected}\n    actual:   {actual}",
+                index + 1
+            )),
+            (Some(expected), None) => differences.push(format!(
+                "request {} was not made\n    expected: {expected}",
+                index + 1
+            )),
+            (None, Some(actual)) => differences.push(format!(
+                "request {} was not expected\n    actual:   {actual}",
+                index + 1
+            )),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    differences
+}
+
+fn matches(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::String(wildcard), _) if wildcard == "*" => true,
+        (Value::Array(expected), Value::Array(actual)) => {
+            expected.len() == actual.len()
+                && expected
+                    .iter()
+                    .zip(actual)
+                    .all(|(expected, actual)| matches(expected, actual))
+        }
+        (Value::Object(expected), Value::Object(actual)) => {
+            expected.len() == actual.len()
+                && expected.iter().all(|(key, expected)| {
+                    actual
+                        .get(key)
+                        .is_some_and(|actual| matches(expected, actual))
+                })
+        }
+        (expected, actual) => expected == actual,
+    }
+}
@@ -0,0 +1,351 @@
+//! Runs the plugin components against [`FakeHost`], a scriptable implementation of the host
+//! functions which records everything a plugin asks of it.
+//!
+//! The components are loaded from the release build of the workspace, build them first with
+//! `just build_release` (the `test` recipe does this for you).
+
+pub mod fixture;
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    path::PathBuf,
+    sync::{LazyLock, Mutex},
+};
+
+use wasmtime::{
+    Engine, Store,
+    component::{Component, Linker, ResourceTable},
+};
+use wasmtime_wasi::{IoView, WasiCtx, WasiCtxBuilder, WasiView};
+
+wasmtime::component::bindgen!({
+    path: "../wit",
+    world: "plugin",
+    additional_derives: [PartialEq],
+});
+
+pub use crate::discord_bot::plugin::{
+    discord_types::{Contents, Error as DiscordError, Events, Requests},
+    host_types::LogLevels,
+    plugin_types::{
+        RegistrationsRequest, RegistrationsRequestDiscordEvents,
+        RegistrationsRequestInteractionCreate, RegistrationsResult, SupportedRegistrations,
+    },
+};
+
+static ENGINE: LazyLock<Engine> = LazyLock::new(Engine::default);
+
+static COMPONENTS: LazyLock<Mutex<HashMap<String, Component>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A job scheduled through schedule-once or schedule-cron.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScheduledJob {
+    Once {
+        time: u64,
+        payload: Option<Vec<u8>>,
+    },
+    Cron {
+        crons: Vec<String>,
+        payload: Option<Vec<u8>>,
+    },
+}
+
+/// The host side of a plugin instance.
+///
+/// Discord requests are answered from `responses` in order, once it runs out every request gets
+/// an empty Ok response. Dependency functions are answered the same way from
+/// `dependency_responses`, with an error once it runs out.
+pub struct FakeHost {
+    pub requests: Vec<Requests>,
+    pub responses: VecDeque<Result<Option<Vec<u8>>, DiscordError>>,
+    pub logs: Vec<(LogLevels, String)>,
+    pub storage: BTreeMap<String, Vec<u8>>,
+    pub scheduled_jobs: BTreeMap<String, ScheduledJob>,
+    pub dependency_calls: Vec<(String, String, Vec<u8>)>,
+    pub dependency_responses: VecDeque<Result<Vec<u8>, String>>,
+    pub shutdown: Option<bool>,
+    wasi: WasiCtx,
+    table: ResourceTable,
+}
+
+impl Default for FakeHost {
+    fn default() -> Self {
+        Self {
+            requests: vec![],
+            responses: VecDeque::new(),
+            logs: vec![],
+            storage: BTreeMap::new(),
+            scheduled_jobs: BTreeMap::new(),
+            dependency_calls: vec![],
+            dependency_responses: VecDeque::new(),
+            shutdown: None,
+            wasi: WasiCtxBuilder::new().inherit_stderr().build(),
+            table: ResourceTable::new(),
+        }
+    }
+}
+
+impl FakeHost {
+    /// Queues a successful response to a Discord request, body is JSON.
+    pub fn respond(&mut self, body: &[u8]) {
+        self.responses.push_back(Ok(Some(body.to_vec())));
+    }
+
+    /// Queues a failed response to a Discord request.
+    pub fn fail(&mut self, status: u16, code: u32, message: &str) {
+        self.responses.push_back(Err(DiscordError {
+            status: Some(status),
+            code: Some(code),
+            message: message.to_string(),
+            retry_after: None,
+        }));
+    }
+
+    /// The logged messages of the provided level.
+    pub fn logs(&self, level: LogLevels) -> Vec<&str> {
+        self.logs
+            .iter()
+            .filter(|(log_level, _)| *log_level == level)
+            .map(|(_, message)| message.as_str())
+            .collect()
+    }
+}
+
+impl IoView for FakeHost {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+impl WasiView for FakeHost {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+impl discord_bot::plugin::host_types::Host for FakeHost {}
+
+impl discord_bot::plugin::discord_types::Host for FakeHost {}
+
+impl discord_bot::plugin::plugin_types::Host for FakeHost {}
+
+impl discord_bot::plugin::host_functions::Host for FakeHost {
+    fn log(&mut self, level: LogLevels, message: String) {
+        self.logs.push((level, message));
+    }
+
+    fn discord_request(&mut self, request: Requests) -> Result<Option<Vec<u8>>, DiscordError> {
+        self.requests.push(request);
+
+        self.responses.pop_front().unwrap_or(Ok(None))
+    }
+
+    fn dependency_function(
+        &mut self,
+        dependency: String,
+        function: String,
+        params: Vec<u8>,
+    ) -> Result<Vec<u8>, String> {
+        self.dependency_calls.push((dependency, function, params));
+
+        self.dependency_responses
+            .pop_front()
+            .unwrap_or(Err(String::from("The dependency is not available.")))
+    }
+
+    fn schedule_once(
+        &mut self,
+        id: String,
+        time: u64,
+        payload: Option<Vec<u8>>,
+    ) -> Result<(), String> {
+        self.scheduled_jobs
+            .insert(id, ScheduledJob::Once { time, payload });
+
+        Ok(())
+    }
+
+    fn schedule_cron(
+        &mut self,
+        id: String,
+        crons: Vec<String>,
+        payload: Option<Vec<u8>>,
+    ) -> Result<(), String> {
+        self.scheduled_jobs
+            .insert(id, ScheduledJob::Cron { crons, payload });
+
+        Ok(())
+    }
+
+    fn cancel_job(&mut self, id: String) -> Result<bool, String> {
+        Ok(self.scheduled_jobs.remove(&id).is_some())
+    }
+
+    fn shutdown(&mut self, restart: bool) {
+        self.shutdown = Some(restart);
+    }
+}
+
+impl discord_bot::plugin::storage::Host for FakeHost {
+    fn get(&mut self, key: String) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.storage.get(&key).cloned())
+    }
+
+    fn set(&mut self, key: String, value: Vec<u8>, _ttl: Option<u64>) -> Result<(), String> {
+        self.storage.insert(key, value);
+
+        Ok(())
+    }
+
+    fn delete(&mut self, key: String) -> Result<bool, String> {
+        Ok(self.storage.remove(&key).is_some())
+    }
+
+    fn list_keys(&mut self, prefix: String) -> Result<Vec<String>, String> {
+        Ok(self
+            .storage
+            .keys()
+            .filter(|key| key.starts_with(&prefix))
+            .cloned()
+            .collect())
+    }
+}
+
+/// An instantiated plugin component, the export calls panic when the plugin traps.
+pub struct PluginInstance {
+    store: Store<FakeHost>,
+    plugin: Plugin,
+}
+
+impl PluginInstance {
+    /// Instantiates the plugin with the provided crate name, e.g. `extension-requests`.
+    pub fn new(name: &str) -> Self {
+        Self::with_host(name, FakeHost::default())
+    }
+
+    pub fn with_host(name: &str, host: FakeHost) -> Self {
+        let component = component(name);
+
+        let mut linker = Linker::new(&ENGINE);
+
+        wasmtime_wasi::add_to_linker_sync(&mut linker).unwrap();
+        Plugin::add_to_linker(&mut linker, |host: &mut FakeHost| host).unwrap();
+
+        // wasi:http is not provided, plugins which use it trap once they send a request.
+        linker.define_unknown_imports_as_traps(&component).unwrap();
+
+        let mut store = Store::new(&ENGINE, host);
+
+        let plugin = match Plugin::instantiate(&mut store, &component, &linker) {
+            Ok(plugin) => plugin,
+            Err(err) => panic!("The {name} plugin could not be instantiated: {err:?}"),
+        };
+
+        Self { store, plugin }
+    }
+
+    pub fn host(&self) -> &FakeHost {
+        self.store.data()
+    }
+
+    pub fn host_mut(&mut self) -> &mut FakeHost {
+        self.store.data_mut()
+    }
+
+    /// Returns the Discord requests made so far and clears them.
+    pub fn take_requests(&mut self) -> Vec<Requests> {
+        std::mem::take(&mut self.store.data_mut().requests)
+    }
+
+    pub fn initialization(
+        &mut self,
+        settings: &[u8],
+        supported_registrations: SupportedRegistrations,
+    ) -> Result<RegistrationsRequest, String> {
+        self.plugin
+            .discord_bot_plugin_plugin_functions()
+            .call_initialization(&mut self.store, settings, supported_registrations)
+            .expect("The plugin trapped")
+    }
+
+    pub fn settings_update(&mut self, settings: &[u8]) -> Result<RegistrationsRequest, String> {
+        self.plugin
+            .discord_bot_plugin_plugin_functions()
+            .call_settings_update(&mut self.store, settings)
+            .expect("The plugin trapped")
+    }
+
+    pub fn settings_schema(&mut self) -> Vec<u8> {
+        self.plugin
+            .discord_bot_plugin_plugin_functions()
+            .call_settings_schema(&mut self.store)
+            .expect("The plugin trapped")
+    }
+
+    pub fn registrations_processed(
+        &mut self,
+        registrations_result: &RegistrationsResult,
+    ) -> Result<(), String> {
+        self.plugin
+            .discord_bot_plugin_plugin_functions()
+            .call_registrations_processed(&mut self.store, registrations_result)
+            .expect("The plugin trapped")
+    }
+
+    pub fn discord_event(&mut self, event: &Events) -> Result<(), String> {
+        self.plugin
+            .discord_bot_plugin_plugin_functions()
+            .call_discord_event(&mut self.store, event)
+            .expect("The plugin trapped")
+    }
+
+    pub fn scheduled_job(&mut self, job: &str, payload: Option<&[u8]>) -> Result<(), String> {
+        self.plugin
+            .discord_bot_plugin_plugin_functions()
+            .call_scheduled_job(&mut self.store, job, payload)
+            .expect("The plugin trapped")
+    }
+
+    pub fn dependency_function(
+        &mut self,
+        function: &str,
+        params: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        self.plugin
+            .discord_bot_plugin_plugin_functions()
+            .call_dependency_function(&mut self.store, function, params)
+            .expect("The plugin trapped")
+    }
+
+    pub fn shutdown(&mut self) -> Result<(), String> {
+        self.plugin
+            .discord_bot_plugin_plugin_functions()
+            .call_shutdown(&mut self.store)
+            .expect("The plugin trapped")
+    }
+}
+
+fn component(name: &str) -> Component {
+    let mut components = COMPONENTS.lock().unwrap();
+
+    if let Some(component) = components.get(name) {
+        return component.clone();
+    }
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../target/wasm32-wasip2/release")
+        .join(format!("{}.wasm", name.replace('-', "_")));
+
+    let component = match Component::from_file(&ENGINE, &path) {
+        Ok(component) => component,
+        Err(err) => panic!(
+            "The {name} component could not be loaded from {}, build the plugins first with `just build_release`: {err:?}",
+            path.display()
+        ),
+    };
+
+    components.insert(name.to_string(), component.clone());
+
+    component
+}
@@ -0,0 +1,144 @@
+use harness::{Contents, Events, PluginInstance, Requests, SupportedRegistrations};
+
+const CHANNEL: &[u8] = br#"{"id":"200","type":0,"guild_id":"100","name":"automod"}"#;
+
+fn message_create(content: &str, attachments: usize) -> Vec<u8> {
+    let attachments = (0..attachments)
+        .map(|id| {
+            format!(
+                r#"{{"id":"{id}","filename":"{id}.png","size":1,"url":"https://cdn.discordapp.com/{id}.png","proxy_url":"https://media.discordapp.net/{id}.png"}}"#
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"{{"id":"300","channel_id":"201","guild_id":"100","author":{{"id":"400","username":"spammer","discriminator":"0","avatar":null}},"member":{{"roles":[],"joined_at":"2025-01-01T00:00:00.000000+00:00","deaf":false,"mute":false,"flags":0}},"content":"{content}","timestamp":"2025-01-01T00:00:00.000000+00:00","edited_timestamp":null,"tts":false,"mention_everyone":false,"mentions":[],"mention_roles":[],"attachments":[{attachments}],"embeds":[],"components":[],"pinned":false,"type":0,"flags":0}}"#
+    )
+    .into_bytes()
+}
+
+fn automod() -> PluginInstance {
+    let mut automod = PluginInstance::new("automod");
+
+    automod.host_mut().respond(CHANNEL);
+
+    automod
+        .initialization(
+            br#"{"automod_channel_id":200}"#,
+            SupportedRegistrations::all(),
+        )
+        .unwrap();
+
+    assert_eq!(automod.take_requests(), vec![Requests::GetChannel(200)]);
+
+    automod
+}
+
+#[test]
+fn attachment_spam_is_deleted_timed_out_and_reported() {
+    let mut automod = automod();
+
+    automod
+        .discord_event(&Events::MessageCreate(message_create("", 4)))
+        .unwrap();
+
+    let requests = automod.take_requests();
+
+    assert_eq!(requests.len(), 3, "{requests:?}");
+    assert_eq!(requests[0], Requests::DeleteMessage((201, 300)));
+    assert!(
+        matches!(&requests[1], Requests::UpdateMember((100, 400, body)) if String::from_utf8_lossy(body).contains("communication_disabled_until")),
+        "{:?}",
+        requests[1]
+    );
+    assert!(
+        matches!(&requests[2], Requests::CreateMessage((200, Contents::Json(body))) if String::from_utf8_lossy(body).contains("Attachment spam (4)")),
+        "{:?}",
+        requests[2]
+    );
+}
+
+#[test]
+fn messages_with_content_are_ignored() {
+    let mut automod = automod();
+
+    automod
+        .discord_event(&Events::MessageCreate(message_create("Look at these", 4)))
+        .unwrap();
+
+    assert!(automod.take_requests().is_empty());
+}
+
+#[test]
+fn failed_actions_are_reported() {
+    let mut automod = automod();
+
+    automod.host_mut().fail(403, 50013, "Missing Permissions");
+
+    automod
+        .discord_event(&Events::MessageCreate(message_create("", 4)))
+        .unwrap();
+
+    let requests = automod.take_requests();
+
+    assert!(
+        matches!(requests.last(), Some(Requests::CreateMessage((200, Contents::Json(body)))) if String::from_utf8_lossy(body).contains("missing Manage Messages permission")),
+        "{requests:?}"
+    );
+}
+
+fn message_update(content: &str, attachments: usize) -> Vec<u8> {
+    String::from_utf8(message_create(content, attachments))
+        .unwrap()
+        .replace(
+            r#""edited_timestamp":null"#,
+            r#""edited_timestamp":"2025-01-01T00:01:00.000000+00:00""#,
+        )
+        .into_bytes()
+}
+
+#[test]
+fn unedited_message_updates_are_ignored() {
+    let mut automod = automod();
+
+    automod
+        .discord_event(&Events::MessageUpdate(message_create("", 4)))
+        .unwrap();
+
+    assert!(automod.take_requests().is_empty());
+}
+
+#[test]
+fn edited_messages_are_validated() {
+    let mut automod = automod();
+
+    automod
+        .discord_event(&Events::MessageCreate(message_create("Look at these", 4)))
+        .unwrap();
+    automod
+        .discord_event(&Events::MessageUpdate(message_update("", 4)))
+        .unwrap();
+
+    let requests = automod.take_requests();
+
+    assert_eq!(requests.len(), 3, "{requests:?}");
+    assert_eq!(requests[0], Requests::DeleteMessage((201, 300)));
+}
+
+#[test]
+fn actioned_messages_are_not_actioned_again() {
+    let mut automod = automod();
+
+    automod
+        .discord_event(&Events::MessageCreate(message_create("", 4)))
+        .unwrap();
+
+    assert_eq!(automod.take_requests().len(), 3);
+
+    automod
+        .discord_event(&Events::MessageUpdate(message_update("", 4)))
+        .unwrap();
+
+    assert!(automod.take_requests().is_empty());
+}
@@ -0,0 +1,592 @@
+use harness::{
+    Contents, Events, FakeHost, LogLevels, PluginInstance, Requests, SupportedRegistrations,
+};
+use serde_json::Value;
+
+fn interaction(kind: u8, data: &str) -> Events {
+    Events::InteractionCreate(
+        format!(
+            r#"{{"id":"3000","application_id":"10","type":{kind},"token":"token","version":1,"data":{data},"channel_id":"201","channel":{{"id":"201","type":1}},"user":{{"id":"400","username":"reader","global_name":"Reader","discriminator":"0","avatar":null,"public_flags":0}},"locale":"en-US","app_permissions":"2248473465835073","entitlements":[],"authorizing_integration_owners":{{"1":"400"}},"context":1}}"#
+        )
+        .into_bytes(),
+    )
+}
+
+fn command(name: &str) -> Events {
+    interaction(2, &format!(r#"{{"id":"9000","name":"{name}","type":1}}"#))
+}
+
+fn button(custom_id: &str) -> Events {
+    interaction(
+        3,
+        &format!(r#"{{"custom_id":"{custom_id}","component_type":2}}"#),
+    )
+}
+
+const PROVIDER: &str =
+    r#""provider":{"kind":"static","urls":["https://example.com/cats/tabby.png"]}"#;
+
+// The static provider makes no HTTP requests, which the harness does not provide.
+fn cats_with(settings: &str) -> PluginInstance {
+    let mut cats = PluginInstance::new("cats");
+
+    cats.initialization(
+        format!("{{{PROVIDER},{settings}}}").as_bytes(),
+        SupportedRegistrations::all(),
+    )
+    .unwrap();
+
+    cats
+}
+
+fn cats() -> PluginInstance {
+    let mut cats = PluginInstance::new("cats");
+
+    let registrations = cats
+        .initialization(
+            br#"{"provider":{"kind":"static","urls":["https://example.com/cats/tabby.png"]}}"#,
+            SupportedRegistrations::all(),
+        )
+        .unwrap();
+
+    let commands = registrations
+        .discord_events
+        .and_then(|discord_events| discord_events.interaction_create)
+        .and_then(|interaction_create| interaction_create.application_commands)
+        .unwrap()
+        .iter()
+        .map(|command| serde_json::from_slice::<Value>(command).unwrap()["name"].clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(commands, ["cat", "cat-stats"]);
+    assert_eq!(
+        registrations.scheduled_jobs,
+        Some(vec![(
+            String::from("cat_pool"),
+            vec![String::from("0 * * * * *")]
+        )])
+    );
+
+    cats
+}
+
+#[test]
+fn request_cat_from_static_provider() {
+    let mut cats = cats();
+
+    let cat = cats.dependency_function("request_cat", b"{}").unwrap();
+
+    assert_eq!(
+        serde_json::from_slice::<Value>(&cat).unwrap(),
+        serde_json::json!({
+            "id": "tabby",
+            "url": "https://example.com/cats/tabby.png",
+            "width": null,
+            "height": null,
+            "mime_type": "image/png",
+            "breed": null,
+        })
+    );
+
+    assert!(cats.take_requests().is_empty());
+}
+
+#[test]
+fn cat_command_is_deferred_when_the_pool_is_empty() {
+    let mut cats = cats();
+
+    cats.discord_event(&command("cat")).unwrap();
+
+    let requests = cats.take_requests();
+
+    assert_eq!(requests.len(), 2, "{requests:?}");
+    assert!(
+        matches!(&requests[0], Requests::InteractionCallback((3000, _, _, body)) if serde_json::from_slice::<Value>(body).unwrap() == serde_json::json!({"type": 5})),
+        "{:?}",
+        requests[0]
+    );
+    assert!(
+        matches!(&requests[1], Requests::UpdateInteractionOriginal((10, _, body)) if String::from_utf8_lossy(body).contains("https://example.com/cats/tabby.png")),
+        "{:?}",
+        requests[1]
+    );
+}
+
+#[test]
+fn errors_after_deferring_are_sent_as_follow_ups() {
+    let mut cats = cats_with(r#""show_error_embeds":false"#);
+
+    cats.host_mut().responses.push_back(Ok(None));
+    cats.host_mut().fail(404, 10015, "Unknown Webhook");
+    cats.host_mut()
+        .fail(400, 40060, "Interaction has already been acknowledged.");
+
+    cats.discord_event(&command("cat")).unwrap();
+
+    let requests = cats.take_requests();
+
+    // The deferred response is not replaced, the error only shows to the user who asked
+    assert_eq!(requests.len(), 4, "{requests:?}");
+    assert!(
+        matches!(&requests[2], Requests::InteractionCallback((3000, _, _, _))),
+        "{:?}",
+        requests[2]
+    );
+
+    let Requests::CreateFollowup((10, _, body)) = &requests[3] else {
+        panic!("{:?}", requests[3]);
+    };
+
+    let followup = serde_json::from_slice::<Value>(body).unwrap();
+
+    assert_eq!(followup["flags"], 64);
+    assert_eq!(followup["embeds"][0]["title"], "Something Went Wrong");
+}
+
+#[test]
+fn cat_command_is_served_from_the_pool() {
+    let mut cats = cats();
+
+    cats.scheduled_job("cat_pool", None).unwrap();
+
+    cats.discord_event(&command("cat")).unwrap();
+
+    let requests = cats.take_requests();
+
+    assert_eq!(requests.len(), 1, "{requests:?}");
+    assert!(
+        matches!(&requests[0], Requests::InteractionCallback((3000, _, _, body)) if String::from_utf8_lossy(body).contains("https://example.com/cats/tabby.png")),
+        "{:?}",
+        requests[0]
+    );
+}
+
+#[test]
+fn static_provider_rejects_filters() {
+    let mut cats = cats();
+
+    let err = cats
+        .dependency_function("request_cat", br#"{"breed":"beng"}"#)
+        .unwrap_err();
+
+    assert_eq!(
+        serde_json::from_str::<Value>(&err).unwrap()["kind"],
+        "request"
+    );
+}
+
+#[test]
+fn static_provider_needs_urls() {
+    let mut cats = PluginInstance::new("cats");
+
+    assert_eq!(
+        cats.initialization(
+            br#"{"provider":{"kind":"static","urls":[]}}"#,
+            SupportedRegistrations::all(),
+        ),
+        Err(String::from(
+            "The static image provider needs at least one URL."
+        ))
+    );
+}
+
+#[test]
+fn overrides_of_unknown_locales_are_not_registered() {
+    let mut cats = PluginInstance::new("cats");
+
+    let registrations = cats
+        .initialization(
+            br#"{"provider":{"kind":"static","urls":["https://example.com/cats/tabby.png"]},"strings":{"nl-BE":{"command.cat.description":"Krijg een kat"},"fr":{"command.cat.description":"Obtenir un chat"}}}"#,
+            SupportedRegistrations::all(),
+        )
+        .unwrap();
+
+    let command = serde_json::from_slice::<Value>(
+        &registrations
+            .discord_events
+            .and_then(|discord_events| discord_events.interaction_create)
+            .and_then(|interaction_create| interaction_create.application_commands)
+            .unwrap()[0],
+    )
+    .unwrap();
+
+    assert_eq!(command["name"], "cat");
+    assert_eq!(
+        command["description_localizations"]["fr"],
+        "Obtenir un chat"
+    );
+    assert!(command["description_localizations"].get("nl-BE").is_none());
+}
+
+const AUTOMATED_CAT: &str =
+    r#""automated_cats":[{"guild_id":"100","channel_id":"200","cron":"0 0 12 * * *"}]"#;
+
+#[test]
+fn automated_cats_are_scheduled_per_channel() {
+    let mut cats = PluginInstance::new("cats");
+
+    let registrations = cats
+        .initialization(
+            format!("{{{PROVIDER},{AUTOMATED_CAT},\"cat_pool_size\":0}}").as_bytes(),
+            SupportedRegistrations::all(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        registrations.scheduled_jobs,
+        Some(vec![(
+            String::from("automated_cat_100_200"),
+            vec![String::from("0 0 12 * * *")]
+        )])
+    );
+
+    let registrations = cats
+        .settings_update(
+            format!(
+                r#"{{{PROVIDER},"automated_cats":[{{"guild_id":"100","channel_id":"200","cron":"0 0 18 * * *"}}],"cat_pool_size":0}}"#
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+
+    // Only the changed registration kinds are returned
+    assert_eq!(registrations.discord_events, None);
+    assert_eq!(
+        registrations.scheduled_jobs,
+        Some(vec![(
+            String::from("automated_cat_100_200"),
+            vec![String::from("0 0 18 * * *")]
+        )])
+    );
+}
+
+#[test]
+fn automated_cats_are_posted_in_their_channel() {
+    let mut cats = cats_with(AUTOMATED_CAT);
+
+    cats.scheduled_job("automated_cat_100_200", None).unwrap();
+
+    let requests = cats.take_requests();
+
+    assert_eq!(requests.len(), 1, "{requests:?}");
+    assert!(
+        matches!(&requests[0], Requests::CreateMessage((200, Contents::Json(body))) if String::from_utf8_lossy(body).contains("https://example.com/cats/tabby.png")),
+        "{:?}",
+        requests[0]
+    );
+}
+
+fn message_create(channel_id: u64, content: &str) -> Events {
+    Events::MessageCreate(
+        format!(
+            r#"{{"id":"300","channel_id":"{channel_id}","guild_id":"100","author":{{"id":"400","username":"reader","discriminator":"0","avatar":null}},"content":"{content}","timestamp":"2025-01-01T00:00:00.000000+00:00","edited_timestamp":null,"tts":false,"mention_everyone":false,"mentions":[],"mention_roles":[],"attachments":[],"embeds":[],"components":[],"pinned":false,"type":0,"flags":0}}"#
+        )
+        .into_bytes(),
+    )
+}
+
+fn cat_messages_detected(cats: &PluginInstance) -> Value {
+    serde_json::from_slice::<Value>(&cats.host().storage["stats"]).unwrap()["cat_messages_detected"]
+        .clone()
+}
+
+#[test]
+fn messages_mentioning_cats_get_a_cat() {
+    let mut cats = cats_with(r#""cat_message_response_chance":100"#);
+
+    cats.discord_event(&message_create(201, "Look at my CATS!"))
+        .unwrap();
+
+    let requests = cats.take_requests();
+
+    assert_eq!(requests.len(), 1, "{requests:?}");
+    assert!(
+        matches!(&requests[0], Requests::CreateMessage((201, Contents::Json(body))) if String::from_utf8_lossy(body).contains("https://example.com/cats/tabby.png")),
+        "{:?}",
+        requests[0]
+    );
+    assert_eq!(cat_messages_detected(&cats), 1);
+}
+
+#[test]
+fn cat_words_are_matched_as_whole_words() {
+    let mut cats = cats_with(r#""cat_message_response_chance":100"#);
+
+    cats.discord_event(&message_create(201, "What category is this, concatenate?"))
+        .unwrap();
+
+    assert!(cats.take_requests().is_empty());
+    assert!(!cats.host().storage.contains_key("stats"));
+}
+
+#[test]
+fn cat_messages_are_detected_without_a_response_chance() {
+    let mut cats = cats_with(r#""cat_message_response_chance":0"#);
+
+    cats.discord_event(&message_create(201, "a kitten"))
+        .unwrap();
+
+    assert!(cats.take_requests().is_empty());
+    assert_eq!(cat_messages_detected(&cats), 1);
+}
+
+#[test]
+fn cat_messages_have_a_cooldown_per_channel() {
+    let mut cats = cats_with(r#""cat_message_response_chance":100"#);
+
+    cats.discord_event(&message_create(201, "cat")).unwrap();
+    cats.discord_event(&message_create(201, "another cat"))
+        .unwrap();
+    cats.discord_event(&message_create(202, "cat")).unwrap();
+
+    let requests = cats.take_requests();
+
+    assert_eq!(requests.len(), 2, "{requests:?}");
+    assert!(matches!(&requests[0], Requests::CreateMessage((201, _))));
+    assert!(matches!(&requests[1], Requests::CreateMessage((202, _))));
+    assert_eq!(cat_messages_detected(&cats), 3);
+}
+
+/// Stats with a leaderboard of 11 users, user 400 + n requested n + 1 cats.
+fn cats_with_stats() -> PluginInstance {
+    let most_cats_demanded = (0..11)
+        .map(|n| format!(r#""{}":{}"#, 400 + n, n + 1))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mut host = FakeHost::default();
+
+    host.storage.insert(
+        String::from("stats"),
+        format!(
+            r#"{{"total_cats_requested":70,"cat_messages_detected":5,"cats_on_demand":66,"automated_cats":4,"most_cats_demanded":{{{most_cats_demanded}}}}}"#
+        )
+        .into_bytes(),
+    );
+
+    let mut cats = PluginInstance::with_host("cats", host);
+
+    cats.initialization(
+        format!("{{{PROVIDER}}}").as_bytes(),
+        SupportedRegistrations::all(),
+    )
+    .unwrap();
+
+    cats
+}
+
+#[test]
+fn corrupt_stats_are_counted_over() {
+    let mut host = FakeHost::default();
+
+    host.storage
+        .insert(String::from("stats"), b"not stats".to_vec());
+
+    let mut cats = PluginInstance::with_host("cats", host);
+
+    cats.initialization(
+        format!("{{{PROVIDER}}}").as_bytes(),
+        SupportedRegistrations::all(),
+    )
+    .unwrap();
+
+    assert!(
+        cats.host()
+            .logs(LogLevels::Warn)
+            .iter()
+            .any(|log| log.starts_with("The stored stats were of the incorrect structure")),
+        "{:?}",
+        cats.host().logs
+    );
+
+    cats.dependency_function("request_cat", b"{}").unwrap();
+
+    let stats = serde_json::from_slice::<Value>(&cats.host().storage["stats"]).unwrap();
+
+    assert_eq!(stats["total_cats_requested"], 1);
+}
+
+fn response(request: &Requests) -> Value {
+    let Requests::InteractionCallback((3000, _, _, body)) = request else {
+        panic!("{request:?}");
+    };
+
+    serde_json::from_slice(body).unwrap()
+}
+
+#[test]
+fn cat_stats_shows_the_counters_and_first_page() {
+    let mut cats = cats_with_stats();
+
+    cats.discord_event(&command("cat-stats")).unwrap();
+
+    let requests = cats.take_requests();
+
+    assert_eq!(requests.len(), 1, "{requests:?}");
+
+    let response = response(&requests[0]);
+    let fields = &response["data"]["embeds"][0]["fields"];
+
+    assert_eq!(response["type"], 4);
+    assert_eq!(fields[0]["value"], "70");
+    assert_eq!(fields[1]["value"], "66");
+    assert_eq!(fields[2]["value"], "5");
+    assert_eq!(fields[3]["value"], "4");
+    assert_eq!(fields[4]["name"], "Top Requesters (1/2)");
+
+    let leaderboard = fields[4]["value"].as_str().unwrap();
+
+    assert!(
+        leaderboard.starts_with("**1.** <@410>: 11\n"),
+        "{leaderboard}"
+    );
+    assert!(leaderboard.ends_with("**10.** <@401>: 2"), "{leaderboard}");
+
+    let buttons = &response["data"]["components"][0]["components"];
+
+    assert_eq!(buttons[0]["custom_id"], "cat-stats:0");
+    assert_eq!(buttons[0]["disabled"], true);
+    assert_eq!(buttons[1]["custom_id"], "cat-stats:1");
+    assert_ne!(buttons[1]["disabled"], true);
+}
+
+#[test]
+fn cat_stats_pages_are_navigated_with_buttons() {
+    let mut cats = cats_with_stats();
+
+    cats.discord_event(&button("cat-stats:1")).unwrap();
+
+    let requests = cats.take_requests();
+
+    assert_eq!(requests.len(), 1, "{requests:?}");
+
+    let response = response(&requests[0]);
+    let fields = &response["data"]["embeds"][0]["fields"];
+
+    // The message the buttons are on is updated
+    assert_eq!(response["type"], 7);
+    assert_eq!(fields[4]["name"], "Top Requesters (2/2)");
+    assert_eq!(fields[4]["value"], "**11.** <@400>: 1");
+
+    let buttons = &response["data"]["components"][0]["components"];
+
+    assert_eq!(buttons[0]["custom_id"], "cat-stats:0");
+    assert_ne!(buttons[0]["disabled"], true);
+    assert_eq!(buttons[1]["disabled"], true);
+}
+
+#[test]
+fn invalid_settings_are_not_applied() {
+    let mut cats = cats_with(r#""cat_message_response_chance":0"#);
+
+    assert!(
+        cats.settings_update(
+            br#"{"provider":{"kind":"static","urls":[]},"cat_message_response_chance":100}"#
+        )
+        .is_err()
+    );
+
+    cats.discord_event(&message_create(201, "cat")).unwrap();
+
+    assert!(cats.take_requests().is_empty());
+}
+
+#[test]
+fn switching_providers_drops_their_cats() {
+    let mut cats = cats();
+
+    cats.scheduled_job("cat_pool", None).unwrap();
+
+    let registrations = cats
+        .settings_update(
+            br#"{"provider":{"kind":"static","urls":["https://example.com/cats/siamese.png"]}}"#,
+        )
+        .unwrap();
+
+    // Same commands, so nothing has to be registered again
+    assert_eq!(registrations.discord_events, None);
+
+    cats.discord_event(&command("cat")).unwrap();
+
+    let requests = cats.take_requests();
+
+    assert_eq!(requests.len(), 2, "{requests:?}");
+    assert!(
+        matches!(&requests[1], Requests::UpdateInteractionOriginal((10, _, body)) if String::from_utf8_lossy(body).contains("https://example.com/cats/siamese.png")),
+        "{:?}",
+        requests[1]
+    );
+}
+
+#[test]
+fn unchanged_providers_keep_their_cats() {
+    let mut cats = cats_with(r#""cat_message_response_chance":0"#);
+
+    cats.scheduled_job("cat_pool", None).unwrap();
+
+    cats.settings_update(format!("{{{PROVIDER},\"cat_message_response_chance\":100}}").as_bytes())
+        .unwrap();
+
+    cats.discord_event(&command("cat")).unwrap();
+
+    let requests = cats.take_requests();
+
+    // Served from the pool, so the interaction is not deferred
+    assert_eq!(requests.len(), 1, "{requests:?}");
+    assert!(
+        matches!(&requests[0], Requests::InteractionCallback((3000, _, _, body)) if String::from_utf8_lossy(body).contains("https://example.com/cats/tabby.png")),
+        "{:?}",
+        requests[0]
+    );
+}
+
+#[test]
+fn switching_to_an_api_provider_registers_its_commands() {
+    let mut cats = cats();
+
+    let registrations = cats
+        .settings_update(br#"{"provider":{"kind":"the_dog_api"}}"#)
+        .unwrap();
+
+    let commands = registrations
+        .discord_events
+        .and_then(|discord_events| discord_events.interaction_create)
+        .and_then(|interaction_create| interaction_create.application_commands)
+        .unwrap()
+        .iter()
+        .map(|command| serde_json::from_slice::<Value>(command).unwrap()["name"].clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        commands,
+        ["cat", "cat-stats", "cat-breed", "cat-favourites"]
+    );
+}
+
+#[test]
+fn cats_are_counted_when_they_are_served() {
+    let mut cats = cats();
+
+    cats.scheduled_job("cat_pool", None).unwrap();
+
+    // Pooled cats are only counted once they are served
+    assert!(!cats.host().storage.contains_key("stats"));
+
+    cats.discord_event(&command("cat")).unwrap();
+
+    let stats = serde_json::from_slice::<Value>(&cats.host().storage["stats"]).unwrap();
+
+    assert_eq!(stats["total_cats_requested"], 1);
+    assert_eq!(stats["cats_on_demand"], 1);
+    assert_eq!(stats["most_cats_demanded"]["400"], 1);
+}
+
+#[test]
+fn unknown_scheduled_jobs_are_rejected() {
+    let mut cats = cats();
+
+    assert_eq!(
+        cats.scheduled_job("dog_pool", None),
+        Err(String::from("Unknown scheduled job: dog_pool"))
+    );
+}
@@ -0,0 +1,24 @@
+use harness::{PluginInstance, Requests, SupportedRegistrations};
+
+const FORUM_CHANNEL: &[u8] = br#"{"id":"2000","type":15,"guild_id":"100","name":"extension-requests","available_tags":[{"id":"1001","name":"Content Service","moderated":false,"emoji_id":null,"emoji_name":null},{"id":"1002","name":"Tracker Service","moderated":false,"emoji_id":null,"emoji_name":null}]}"#;
+
+#[test]
+fn fixtures() {
+    let mut extension_requests = PluginInstance::new("extension-requests");
+
+    extension_requests.host_mut().respond(FORUM_CHANNEL);
+
+    extension_requests
+        .initialization(
+            br#"{"channel_id":2000,"tags":{"content_service":1001,"tracker_service":1002}}"#,
+            SupportedRegistrations::all(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        extension_requests.take_requests(),
+        vec![Requests::GetChannel(2000)]
+    );
+
+    extension_requests.replay("extension-requests");
+}
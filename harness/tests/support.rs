@@ -0,0 +1,21 @@
+use harness::{PluginInstance, Requests, SupportedRegistrations};
+
+const FORUM_CHANNEL: &[u8] = br#"{"id":"2000","type":15,"guild_id":"100","name":"support","available_tags":[{"id":"1001","name":"Question","moderated":false,"emoji_id":null,"emoji_name":null},{"id":"1002","name":"Bug","moderated":false,"emoji_id":null,"emoji_name":null},{"id":"1003","name":"Enhancement","moderated":false,"emoji_id":null,"emoji_name":null},{"id":"1004","name":"Needs Triage","moderated":true,"emoji_id":null,"emoji_name":null}]}"#;
+
+#[test]
+fn fixtures() {
+    let mut support = PluginInstance::new("support");
+
+    support.host_mut().respond(FORUM_CHANNEL);
+
+    support
+        .initialization(
+            br#"{"channel_id":2000,"tags":{"question":1001,"bug":1002,"enhancement":1003,"needs_triage":1004}}"#,
+            SupportedRegistrations::all(),
+        )
+        .unwrap();
+
+    assert_eq!(support.take_requests(), vec![Requests::GetChannel(2000)]);
+
+    support.replay("support");
+}
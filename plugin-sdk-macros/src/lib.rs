@@ -0,0 +1,482 @@
+//! Procedural macros re-exported by the plugin SDK.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Error, Field, Fields, GenericArgument, LitBool, LitInt, LitStr,
+    PathArguments, Type, meta::ParseNestedMeta, parse_macro_input,
+};
+
+/// Derives `plugin_sdk::modal::Modal`, which builds the modal and parses its submission.
+///
+/// The struct needs a `#[modal(id = "...", title = "...")]` attribute and every field a
+/// `#[modal(...)]` attribute starting with the component kind:
+///
+/// - `text_input`: a `String` field, or an `Option<String>` field for optional inputs. Supports
+///   `paragraph`, `placeholder`, `min_length` and `max_length`.
+/// - `string_select`: a `Vec<String>` field. Supports `placeholder`, `min_values`, `max_values`
+///   and one or more `option(value = "...", label = "...")`, which in turn support `description`,
+///   `emoji` and `default`.
+/// - `file_upload`: a `Vec<Id<AttachmentMarker>>` field. Supports `min_values` and `max_values`.
+///
+/// All kinds require a `label` and support `description`, `required` and `custom_id`. The custom
+/// ID defaults to the field name with underscores replaced by dashes.
+///
+/// The texts are the `plugin_sdk::locale::DEFAULT_LOCALE` strings, other locales are looked up
+/// in the plugin catalogs under `<id>.title`, `<id>.<custom ID>.label`, `.description` and
+/// `.placeholder`, and `<id>.<custom ID>.<option value>.label` and `.description`.
+///
+/// ```ignore
+/// #[derive(Modal)]
+/// #[modal(id = "support-question", title = "Support Question")]
+/// struct SupportQuestionModal {
+///     #[modal(text_input, label = "Title", placeholder = "What... How... Why...")]
+///     title: String,
+///     #[modal(file_upload, label = "File Upload", required = false)]
+///     files: Vec<Id<AttachmentMarker>>,
+/// }
+/// ```
+#[proc_macro_derive(Modal, attributes(modal))]
+pub fn derive_modal(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_modal(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+enum ComponentKind {
+    TextInput,
+    StringSelect,
+    FileUpload,
+}
+
+struct SelectOption {
+    value: LitStr,
+    label: LitStr,
+    description: Option<LitStr>,
+    emoji: Option<LitStr>,
+    default: bool,
+}
+
+struct ModalField {
+    kind: ComponentKind,
+    custom_id: String,
+    label: LitStr,
+    description: Option<LitStr>,
+    placeholder: Option<LitStr>,
+    paragraph: bool,
+    required: Option<bool>,
+    min: Option<LitInt>,
+    max: Option<LitInt>,
+    options: Vec<SelectOption>,
+    optional: bool,
+}
+
+fn expand_modal(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "Modal can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "Modal can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut id = None;
+    let mut title = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("modal"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                id = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("title") {
+                title = Some(meta.value()?.parse::<LitStr>()?);
+            } else {
+                return Err(meta.error("unsupported modal attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let Some(id) = id else {
+        return Err(Error::new_spanned(
+            input,
+            "missing `#[modal(id = \"...\")]` attribute",
+        ));
+    };
+
+    let Some(title) = title else {
+        return Err(Error::new_spanned(
+            input,
+            "missing `#[modal(title = \"...\")]` attribute",
+        ));
+    };
+
+    let title = text(format!("{}.title", id.value()), &title);
+
+    let mut components = vec![];
+    let mut parsers = vec![];
+
+    for field in &fields.named {
+        let modal_field = parse_field(field)?;
+        let ident = field.ident.as_ref().unwrap();
+
+        components.push(component(&id.value(), &modal_field));
+        parsers.push(parser(&modal_field, ident));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::plugin_sdk::modal::Modal for #name #ty_generics #where_clause {
+            const CUSTOM_ID: &'static str = #id;
+
+            fn modal(
+                locales: &::plugin_sdk::locale::Locales,
+                locale: &str,
+            ) -> ::plugin_sdk::twilight_model::http::interaction::InteractionResponseData {
+                ::plugin_sdk::twilight_model::http::interaction::InteractionResponseData {
+                    components: Some(vec![#(#components),*]),
+                    custom_id: Some(String::from(#id)),
+                    title: Some(#title),
+                    ..Default::default()
+                }
+            }
+
+            fn parse(
+                modal_interaction_data: &::plugin_sdk::twilight_model::application::interaction::modal::ModalInteractionData,
+            ) -> Result<Self, String> {
+                if modal_interaction_data.custom_id != #id {
+                    return Err(format!(
+                        "Expected a submission of the {} modal, received {}.",
+                        #id, modal_interaction_data.custom_id
+                    ));
+                }
+
+                Ok(Self {
+                    #(#parsers),*
+                })
+            }
+        }
+    })
+}
+
+fn parse_field(field: &Field) -> Result<ModalField, Error> {
+    let ident = field.ident.as_ref().unwrap();
+
+    let Some(attr) = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("modal"))
+    else {
+        return Err(Error::new_spanned(
+            field,
+            "every field needs a `#[modal(...)]` attribute",
+        ));
+    };
+
+    let mut kind = None;
+    let mut custom_id = None;
+    let mut label = None;
+    let mut description = None;
+    let mut placeholder = None;
+    let mut paragraph = false;
+    let mut required = None;
+    let mut min = None;
+    let mut max = None;
+    let mut options = vec![];
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("text_input") {
+            kind = Some(ComponentKind::TextInput);
+        } else if meta.path.is_ident("string_select") {
+            kind = Some(ComponentKind::StringSelect);
+        } else if meta.path.is_ident("file_upload") {
+            kind = Some(ComponentKind::FileUpload);
+        } else if meta.path.is_ident("custom_id") {
+            custom_id = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("label") {
+            label = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("description") {
+            description = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("placeholder") {
+            placeholder = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("paragraph") {
+            paragraph = true;
+        } else if meta.path.is_ident("required") {
+            required = Some(meta.value()?.parse::<LitBool>()?.value);
+        } else if meta.path.is_ident("min_length") || meta.path.is_ident("min_values") {
+            min = Some(meta.value()?.parse::<LitInt>()?);
+        } else if meta.path.is_ident("max_length") || meta.path.is_ident("max_values") {
+            max = Some(meta.value()?.parse::<LitInt>()?);
+        } else if meta.path.is_ident("option") {
+            options.push(parse_option(&meta)?);
+        } else {
+            return Err(meta.error("unsupported modal field attribute"));
+        }
+
+        Ok(())
+    })?;
+
+    let Some(kind) = kind else {
+        return Err(Error::new_spanned(
+            attr,
+            "expected `text_input`, `string_select` or `file_upload`",
+        ));
+    };
+
+    let Some(label) = label else {
+        return Err(Error::new_spanned(attr, "missing `label = \"...\"`"));
+    };
+
+    if matches!(kind, ComponentKind::StringSelect) && options.is_empty() {
+        return Err(Error::new_spanned(
+            attr,
+            "a string select needs at least one `option(...)`",
+        ));
+    }
+
+    Ok(ModalField {
+        optional: matches!(kind, ComponentKind::TextInput) && is_option(&field.ty),
+        kind,
+        custom_id: custom_id.unwrap_or_else(|| ident.to_string().replace('_', "-")),
+        label,
+        description,
+        placeholder,
+        paragraph,
+        required,
+        min,
+        max,
+        options,
+    })
+}
+
+fn parse_option(meta: &ParseNestedMeta) -> Result<SelectOption, Error> {
+    let mut value = None;
+    let mut label = None;
+    let mut description = None;
+    let mut emoji = None;
+    let mut default = false;
+
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("value") {
+            value = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("label") {
+            label = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("description") {
+            description = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("emoji") {
+            emoji = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("default") {
+            default = true;
+        } else {
+            return Err(meta.error("unsupported option attribute"));
+        }
+
+        Ok(())
+    })?;
+
+    let (Some(value), Some(label)) = (value, label) else {
+        return Err(meta.error("an option needs a `value` and a `label`"));
+    };
+
+    Ok(SelectOption {
+        value,
+        label,
+        description,
+        emoji,
+        default,
+    })
+}
+
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+
+    type_path.path.segments.last().is_some_and(|segment| {
+        segment.ident == "Option"
+            && matches!(
+                &segment.arguments,
+                PathArguments::AngleBracketed(arguments)
+                    if matches!(arguments.args.first(), Some(GenericArgument::Type(_)))
+            )
+    })
+}
+
+/// Looks the text up in the catalogs, the literal is the default locale string.
+fn text(key: String, default: &LitStr) -> TokenStream2 {
+    quote! { locales.text_or(locale, #key, #default) }
+}
+
+fn optional_text(key: String, default: Option<&LitStr>) -> TokenStream2 {
+    match default {
+        Some(default) => {
+            let text = text(key, default);
+
+            quote! { Some(#text) }
+        }
+        None => quote! { None },
+    }
+}
+
+fn optional_int(value: Option<&LitInt>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+fn component(modal_id: &str, field: &ModalField) -> TokenStream2 {
+    let message = quote! { ::plugin_sdk::twilight_model::channel::message };
+
+    let custom_id = &field.custom_id;
+    let key = format!("{modal_id}.{custom_id}");
+    let label = text(format!("{key}.label"), &field.label);
+    let description = optional_text(format!("{key}.description"), field.description.as_ref());
+    let placeholder = optional_text(format!("{key}.placeholder"), field.placeholder.as_ref());
+    let min = optional_int(field.min.as_ref());
+    let max = optional_int(field.max.as_ref());
+
+    let inner = match field.kind {
+        ComponentKind::TextInput => {
+            let required = field.required.unwrap_or(!field.optional);
+            let style = if field.paragraph {
+                quote! { Paragraph }
+            } else {
+                quote! { Short }
+            };
+
+            quote! {
+                #message::Component::TextInput(#message::component::TextInput {
+                    id: None,
+                    custom_id: String::from(#custom_id),
+                    max_length: #max,
+                    min_length: #min,
+                    placeholder: #placeholder,
+                    required: Some(#required),
+                    style: #message::component::TextInputStyle::#style,
+                    value: None,
+                    #[allow(deprecated)]
+                    label: None,
+                })
+            }
+        }
+        ComponentKind::StringSelect => {
+            let required = field.required.unwrap_or(true);
+            let options = field.options.iter().map(|option| {
+                let SelectOption {
+                    value,
+                    label,
+                    default,
+                    ..
+                } = option;
+                let key = format!("{key}.{}", value.value());
+                let label = text(format!("{key}.label"), label);
+                let description =
+                    optional_text(format!("{key}.description"), option.description.as_ref());
+                let emoji = match &option.emoji {
+                    Some(emoji) => quote! {
+                        Some(#message::EmojiReactionType::Unicode {
+                            name: String::from(#emoji),
+                        })
+                    },
+                    None => quote! { None },
+                };
+
+                quote! {
+                    #message::component::SelectMenuOption {
+                        default: #default,
+                        description: #description,
+                        emoji: #emoji,
+                        label: #label,
+                        value: String::from(#value),
+                    }
+                }
+            });
+
+            quote! {
+                #message::Component::SelectMenu(#message::component::SelectMenu {
+                    id: None,
+                    channel_types: None,
+                    custom_id: String::from(#custom_id),
+                    default_values: None,
+                    disabled: false,
+                    kind: #message::component::SelectMenuType::Text,
+                    max_values: #max,
+                    min_values: #min,
+                    options: Some(vec![#(#options),*]),
+                    placeholder: #placeholder,
+                    required: Some(#required),
+                })
+            }
+        }
+        ComponentKind::FileUpload => {
+            let required = field.required.unwrap_or(true);
+
+            quote! {
+                #message::Component::FileUpload(#message::component::FileUpload {
+                    id: None,
+                    custom_id: String::from(#custom_id),
+                    max_values: #max,
+                    min_values: #min,
+                    required: Some(#required),
+                })
+            }
+        }
+    };
+
+    quote! {
+        #message::Component::Label(#message::component::Label {
+            id: None,
+            label: #label,
+            description: #description,
+            component: Box::new(#inner),
+        })
+    }
+}
+
+fn parser(field: &ModalField, ident: &syn::Ident) -> TokenStream2 {
+    let custom_id = &field.custom_id;
+
+    let value = match field.kind {
+        ComponentKind::TextInput if field.optional => quote! {
+            ::plugin_sdk::modal::text_input(modal_interaction_data, #custom_id)
+                .ok()
+                .filter(|value| !value.is_empty())
+        },
+        ComponentKind::TextInput => quote! {
+            ::plugin_sdk::modal::text_input(modal_interaction_data, #custom_id)?
+        },
+        ComponentKind::StringSelect if field.required == Some(false) => quote! {
+            ::plugin_sdk::modal::string_select(modal_interaction_data, #custom_id)
+                .unwrap_or_default()
+        },
+        ComponentKind::StringSelect => quote! {
+            ::plugin_sdk::modal::string_select(modal_interaction_data, #custom_id)?
+        },
+        ComponentKind::FileUpload if field.required == Some(false) => quote! {
+            ::plugin_sdk::modal::file_upload(modal_interaction_data, #custom_id)
+                .unwrap_or_default()
+        },
+        ComponentKind::FileUpload => quote! {
+            ::plugin_sdk::modal::file_upload(modal_interaction_data, #custom_id)?
+        },
+    };
+
+    quote! { #ident: #value }
+}
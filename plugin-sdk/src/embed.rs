@@ -0,0 +1,99 @@
+use std::time::UNIX_EPOCH;
+
+use twilight_model::{
+    application::interaction::Interaction,
+    channel::message::{
+        Embed,
+        embed::{EmbedAuthor, EmbedFooter},
+    },
+    user::User,
+    util::Timestamp,
+};
+
+use crate::{
+    error::PluginError,
+    locale::{self, Locales},
+};
+
+/// The color used by all plugin embeds.
+pub const COLOR: u32 = 0x00E7_2323;
+
+/// An embed with the plugin color and the current time as timestamp.
+pub fn base() -> Embed {
+    Embed {
+        author: None,
+        color: Some(COLOR),
+        description: None,
+        fields: vec![],
+        footer: None,
+        image: None,
+        kind: String::from("rich"),
+        provider: None,
+        thumbnail: None,
+        timestamp: Some(now()),
+        title: None,
+        url: None,
+        video: None,
+    }
+}
+
+/// A [`base`] embed with a footer crediting the user who triggered the interaction.
+pub fn requested_by(locales: &Locales, interaction: &Interaction) -> Embed {
+    let mut embed = base();
+
+    embed.footer = interaction.author().map(|author| EmbedFooter {
+        icon_url: avatar_url(author),
+        proxy_icon_url: None,
+        text: locales.format(
+            locale::of(interaction),
+            "embed.requested-by",
+            &[("user", &author.name)],
+        ),
+    });
+
+    embed
+}
+
+/// A [`requested_by`] embed describing the error.
+pub fn error(locales: &Locales, interaction: &Interaction, err: &PluginError) -> Embed {
+    let mut embed = requested_by(locales, interaction);
+
+    embed.title = Some(locales.text(locale::of(interaction), "embed.error.title"));
+    embed.description = Some(err.to_string());
+
+    embed
+}
+
+/// The user as embed author, with their avatar as icon.
+pub fn author(user: &User) -> EmbedAuthor {
+    EmbedAuthor {
+        icon_url: avatar_url(user),
+        name: user.name.clone(),
+        proxy_icon_url: None,
+        url: None,
+    }
+}
+
+/// The CDN URL of the user avatar, if they have one.
+pub fn avatar_url(user: &User) -> Option<String> {
+    user.avatar.map(|avatar| {
+        format!(
+            "https://cdn.discordapp.com/avatars/{}/{}.webp",
+            user.id.get(),
+            avatar
+        )
+    })
+}
+
+/// The current time, the Unix epoch if the system clock is unavailable.
+pub fn now() -> Timestamp {
+    Timestamp::from_secs(
+        UNIX_EPOCH
+            .elapsed()
+            .unwrap_or_default()
+            .as_secs()
+            .try_into()
+            .unwrap_or_default(),
+    )
+    .unwrap_or(Timestamp::from_secs(0).unwrap())
+}
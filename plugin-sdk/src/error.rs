@@ -0,0 +1,74 @@
+//! The error type of the event paths, so they can return instead of unwrapping Discord data.
+
+use std::fmt::{self, Display, Formatter};
+
+use serde::de::DeserializeOwned;
+
+use crate::discord_bot::plugin::{
+    discord_types::Error as DiscordError, host_functions::log, host_types::LogLevels,
+};
+
+/// [Discord JSON Error Code] for interactions which were already acknowledged.
+///
+/// [Discord JSON Error Code]: https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
+pub const ALREADY_ACKNOWLEDGED: u32 = 40060;
+
+#[derive(Debug)]
+pub enum PluginError {
+    /// Discord responded to a request with an error.
+    Discord(DiscordError),
+    /// A request could not be built.
+    Request(String),
+    /// The named event, response or stored value was of the incorrect structure.
+    Deserialize(&'static str, String),
+    /// The named data was missing from an event or response, e.g. the member of a DM message.
+    Missing(&'static str),
+    /// Anything else, the message is shown to users as is.
+    Other(String),
+}
+
+impl PluginError {
+    /// Logs the error through the host.
+    pub fn log(&self) {
+        log(LogLevels::Error, &self.to_string());
+    }
+}
+
+impl Display for PluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Discord(err) => f.write_str(&String::from(err.clone())),
+            Self::Request(err) | Self::Other(err) => f.write_str(err),
+            Self::Deserialize(name, err) => {
+                write!(f, "The {name} was of the incorrect structure, error: {err}")
+            }
+            Self::Missing(name) => write!(f, "The {name} was missing."),
+        }
+    }
+}
+
+impl From<DiscordError> for PluginError {
+    fn from(err: DiscordError) -> Self {
+        Self::Discord(err)
+    }
+}
+
+impl From<String> for PluginError {
+    fn from(err: String) -> Self {
+        Self::Other(err)
+    }
+}
+
+impl From<PluginError> for String {
+    fn from(err: PluginError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Deserializes JSON, name is used in the error, e.g. `message` or `get channel response`.
+pub fn deserialize<T: DeserializeOwned>(json: &[u8], name: &'static str) -> Result<T, PluginError> {
+    match sonic_rs::from_slice::<T>(json) {
+        Ok(value) => Ok(value),
+        Err(err) => Err(PluginError::Deserialize(name, err.to_string())),
+    }
+}
@@ -0,0 +1,31 @@
+use twilight_model::channel::{Channel, ChannelType};
+
+use crate::{discord_bot::plugin::discord_types::Requests, error::PluginError, request};
+
+/// Gets the channel and makes sure it is a forum channel which has all provided tags available.
+///
+/// tags: tuple entry 0 is the name used in errors and entry 1 is the tag ID.
+pub fn get_channel(channel_id: u64, tags: &[(&str, u64)]) -> Result<Channel, PluginError> {
+    let channel = request::fetch::<Channel>(&Requests::GetChannel(channel_id), "forum channel")?;
+
+    if channel.kind != ChannelType::GuildForum {
+        return Err(PluginError::Other(String::from(
+            "The provided channel needs to be of the forum type.",
+        )));
+    }
+
+    for (name, tag) in tags {
+        if !channel
+            .available_tags
+            .iter()
+            .flatten()
+            .any(|forum_tag| forum_tag.id == *tag)
+        {
+            return Err(PluginError::Other(format!(
+                "The provided {name} tag ID was not available in the provided forum channel."
+            )));
+        }
+    }
+
+    Ok(channel)
+}
@@ -0,0 +1,208 @@
+use twilight_model::{
+    application::{command::CommandOptionChoice, interaction::Interaction},
+    channel::message::{Embed, MessageFlags},
+    http::interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
+};
+
+use crate::{
+    discord_bot::plugin::{
+        discord_types::{Error as DiscordError, Requests},
+        host_functions::discord_request,
+    },
+    embed,
+    error::{ALREADY_ACKNOWLEDGED, PluginError},
+    locale::Locales,
+    request,
+};
+
+/// Sends the interaction callback with the provided response.
+pub fn respond(
+    interaction: &Interaction,
+    response: &InteractionResponse,
+) -> Result<(), DiscordError> {
+    discord_request(&Requests::InteractionCallback((
+        interaction.id.get(),
+        interaction.token.clone(),
+        true,
+        sonic_rs::to_vec(response).unwrap(),
+    )))?;
+
+    Ok(())
+}
+
+/// Responds to the interaction with a message.
+pub fn reply(interaction: &Interaction, data: InteractionResponseData) -> Result<(), DiscordError> {
+    respond(
+        interaction,
+        &InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(data),
+        },
+    )
+}
+
+/// Responds to the interaction with a modal, data should contain the custom ID, title and
+/// components.
+pub fn modal(interaction: &Interaction, data: InteractionResponseData) -> Result<(), DiscordError> {
+    respond(
+        interaction,
+        &InteractionResponse {
+            kind: InteractionResponseType::Modal,
+            data: Some(data),
+        },
+    )
+}
+
+/// Responds to an autocomplete interaction with the provided choices, Discord shows at most 25.
+pub fn autocomplete(
+    interaction: &Interaction,
+    choices: Vec<CommandOptionChoice>,
+) -> Result<(), DiscordError> {
+    respond(
+        interaction,
+        &InteractionResponse {
+            kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            data: Some(InteractionResponseData {
+                choices: Some(choices),
+                ..Default::default()
+            }),
+        },
+    )
+}
+
+/// Responds to a message component interaction by editing the message the component is on.
+pub fn update_message(
+    interaction: &Interaction,
+    data: InteractionResponseData,
+) -> Result<(), DiscordError> {
+    respond(
+        interaction,
+        &InteractionResponse {
+            kind: InteractionResponseType::UpdateMessage,
+            data: Some(data),
+        },
+    )
+}
+
+/// Acknowledges the interaction with a loading state, the actual response is sent later on with
+/// [`update_original`]. Gives more time than the three seconds Discord allows for a reply.
+pub fn defer(interaction: &Interaction) -> Result<(), DiscordError> {
+    respond(
+        interaction,
+        &InteractionResponse {
+            kind: InteractionResponseType::DeferredChannelMessageWithSource,
+            data: None,
+        },
+    )
+}
+
+/// Acknowledges a message component interaction without a loading state, the message the
+/// component is on is edited later on with [`update_original`].
+pub fn defer_update(interaction: &Interaction) -> Result<(), DiscordError> {
+    respond(
+        interaction,
+        &InteractionResponse {
+            kind: InteractionResponseType::DeferredUpdateMessage,
+            data: None,
+        },
+    )
+}
+
+/// Acknowledges the interaction with an ephemeral loading state, the actual response is sent
+/// later on with [`update_original`].
+pub fn defer_ephemeral(interaction: &Interaction) -> Result<(), DiscordError> {
+    respond(
+        interaction,
+        &InteractionResponse {
+            kind: InteractionResponseType::DeferredChannelMessageWithSource,
+            data: Some(InteractionResponseData {
+                flags: Some(MessageFlags::EPHEMERAL),
+                ..Default::default()
+            }),
+        },
+    )
+}
+
+/// body is the JSON of the [Edit Original Interaction Response] request.
+///
+/// [Edit Original Interaction Response]: https://discord.com/developers/docs/interactions/receiving-and-responding#edit-original-interaction-response
+pub fn update_original(interaction: &Interaction, body: Vec<u8>) -> Result<(), DiscordError> {
+    discord_request(&Requests::UpdateInteractionOriginal((
+        interaction.application_id.get(),
+        interaction.token.clone(),
+        body,
+    )))?;
+
+    Ok(())
+}
+
+/// Sends a follow-up message, body is the JSON of the [Create Followup Message] request. Used to
+/// show something next to a deferred response without replacing it.
+///
+/// [Create Followup Message]: https://discord.com/developers/docs/interactions/receiving-and-responding#create-followup-message
+pub fn followup(interaction: &Interaction, body: Vec<u8>) -> Result<(), DiscordError> {
+    discord_request(&Requests::CreateFollowup((
+        interaction.application_id.get(),
+        interaction.token.clone(),
+        body,
+    )))?;
+
+    Ok(())
+}
+
+/// Replaces the embeds of the original response, mostly used after [`defer_ephemeral`].
+pub fn update_original_embeds(
+    interaction: &Interaction,
+    embeds: &[Embed],
+) -> Result<(), PluginError> {
+    let body = request::body(
+        request::client()
+            .interaction(interaction.application_id)
+            .update_response(&interaction.token)
+            .embeds(Some(embeds)),
+    )?;
+
+    update_original(interaction, body)?;
+
+    Ok(())
+}
+
+/// Shows the error to the user as an embed, as a reply or as a follow-up message when the
+/// interaction was already acknowledged. The deferred response is left as is, replacing it would
+/// ignore `ephemeral` and could overwrite the message a component is on.
+pub fn error(
+    locales: &Locales,
+    interaction: &Interaction,
+    err: &PluginError,
+    ephemeral: bool,
+) -> Result<(), PluginError> {
+    let embed = embed::error(locales, interaction, err);
+
+    match reply(
+        interaction,
+        InteractionResponseData {
+            embeds: Some(vec![embed.clone()]),
+            flags: ephemeral.then_some(MessageFlags::EPHEMERAL),
+            ..Default::default()
+        },
+    ) {
+        Ok(()) => Ok(()),
+        Err(err) if err.code == Some(ALREADY_ACKNOWLEDGED) => {
+            let embeds = [embed];
+
+            let mut request = request::client()
+                .interaction(interaction.application_id)
+                .create_followup(&interaction.token)
+                .embeds(&embeds);
+
+            if ephemeral {
+                request = request.flags(MessageFlags::EPHEMERAL);
+            }
+
+            followup(interaction, request::body(request)?)?;
+
+            Ok(())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,42 @@
+//! Shared bindings and helpers for the plugins in this repository.
+//!
+//! Plugins implement the generated `Guest` trait and export it with
+//! `plugin_sdk::export!(Plugin);`.
+
+pub mod embed;
+pub mod error;
+pub mod forum;
+pub mod interaction;
+pub mod locale;
+pub mod modal;
+pub mod registrations;
+pub mod request;
+pub mod router;
+
+// Used by the code generated by the derive macros.
+#[doc(hidden)]
+pub use twilight_model;
+
+// Use a procedural macro to generate bindings for the world we specified in
+// `../wit/world.wit`, the export macro is public so the plugins can use it.
+wit_bindgen::generate!({
+    path: "../wit",
+    additional_derives: [PartialEq],
+    pub_export_macro: true,
+    default_bindings_module: "plugin_sdk",
+});
+
+use discord_bot::plugin::discord_types::Error as DiscordError;
+
+impl From<DiscordError> for String {
+    fn from(err: DiscordError) -> Self {
+        match (err.status, err.code) {
+            (Some(status), Some(code)) => {
+                format!("{} (status: {status}, code: {code})", err.message)
+            }
+            (Some(status), None) => format!("{} (status: {status})", err.message),
+            (None, Some(code)) => format!("{} (code: {code})", err.message),
+            (None, None) => err.message,
+        }
+    }
+}
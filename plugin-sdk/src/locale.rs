@@ -0,0 +1,203 @@
+//! Localized strings from JSON message catalogs compiled into the plugin, see [`locales!`].
+//!
+//! A catalog is a flat JSON object of string keys to strings, placeholders are written as
+//! `{name}`. Strings are looked up in the overrides from the settings first, then in the
+//! catalogs, first for the exact locale, then for its language and finally for
+//! [`DEFAULT_LOCALE`].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    sync::{LazyLock, RwLock},
+};
+
+use twilight_model::application::interaction::Interaction;
+
+/// The locale of the strings in the code, every catalog falls back to it.
+pub const DEFAULT_LOCALE: &str = "en-US";
+
+/// The [locales Discord supports], localization maps with any other locale are rejected.
+///
+/// [locales Discord supports]: https://discord.com/developers/docs/reference#locales
+pub const DISCORD_LOCALES: &[&str] = &[
+    "id", "da", "de", "en-GB", "en-US", "es-ES", "es-419", "fr", "hr", "it", "lt", "hu", "nl",
+    "no", "pl", "pt-BR", "ro", "fi", "sv-SE", "vi", "tr", "cs", "el", "bg", "ru", "uk", "hi", "th",
+    "zh-CN", "ja", "zh-TW", "ko",
+];
+
+/// The catalogs of the SDK itself, the plugin catalogs are added on top of them.
+const SDK_CATALOGS: &[(&str, &str)] = &[
+    ("en-US", include_str!("../locales/en-US.json")),
+    ("nl", include_str!("../locales/nl.json")),
+];
+
+/// Strings keyed by locale and then by string key.
+pub type Catalogs = BTreeMap<String, BTreeMap<String, String>>;
+
+pub(crate) static SDK: LazyLock<Locales> = LazyLock::new(|| Locales::new(&[]));
+
+/// Builds the [`Locales`] of a plugin from `locales/<locale>.json` in the plugin crate.
+///
+/// ```ignore
+/// static LOCALES: LazyLock<Locales> = LazyLock::new(|| plugin_sdk::locales!("en-US", "nl"));
+/// ```
+#[macro_export]
+macro_rules! locales {
+    ($($locale:literal),* $(,)?) => {
+        $crate::locale::Locales::new(&[$((
+            $locale,
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/", $locale, ".json")),
+        )),*])
+    };
+}
+
+pub struct Locales {
+    catalogs: Catalogs,
+    overrides: RwLock<Catalogs>,
+}
+
+impl Locales {
+    /// catalogs: tuple entry 0 is the locale and entry 1 the catalog JSON. Panics on invalid
+    /// catalogs, they are part of the plugin.
+    pub fn new(catalogs: &[(&str, &str)]) -> Self {
+        let mut locales = Catalogs::new();
+
+        for (locale, catalog) in SDK_CATALOGS.iter().chain(catalogs) {
+            let catalog = match sonic_rs::from_str::<BTreeMap<String, String>>(catalog) {
+                Ok(catalog) => catalog,
+                Err(err) => panic!("The {locale} catalog was of the incorrect structure: {err}"),
+            };
+
+            locales
+                .entry(locale.to_string())
+                .or_default()
+                .extend(catalog);
+        }
+
+        Self {
+            catalogs: locales,
+            overrides: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Replaces the overrides, usually from the `strings` setting.
+    pub fn set_overrides(&self, overrides: Catalogs) {
+        *self.overrides.write().unwrap() = overrides;
+    }
+
+    /// The string for the locale, the key itself when no catalog has it.
+    pub fn text(&self, locale: &str, key: &str) -> String {
+        self.lookup(locale, key).unwrap_or_else(|| key.to_string())
+    }
+
+    /// The string for the locale, the provided default when no catalog has it. Used for strings
+    /// which are written in the code, like the modal attributes.
+    pub fn text_or(&self, locale: &str, key: &str, default: &str) -> String {
+        self.lookup(locale, key)
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    /// The string for the locale with its `{name}` placeholders replaced. The placeholders are
+    /// replaced in a single pass, so arguments containing placeholders are left as is.
+    pub fn format(&self, locale: &str, key: &str, arguments: &[(&str, &dyn Display)]) -> String {
+        let text = self.text(locale, key);
+
+        let mut formatted = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+
+        while let Some(start) = rest.find('{') {
+            formatted.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let argument = rest.find('}').and_then(|end| {
+                arguments
+                    .iter()
+                    .find(|(name, _)| *name == &rest[1..end])
+                    .map(|(_, value)| (end, value))
+            });
+
+            match argument {
+                Some((end, value)) => {
+                    formatted.push_str(&value.to_string());
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    formatted.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        formatted.push_str(rest);
+
+        formatted
+    }
+
+    /// The translations of the key in every [Discord locale](DISCORD_LOCALES) except
+    /// [`DEFAULT_LOCALE`], for the localization maps of commands. None when there are no
+    /// translations.
+    pub fn localizations(&self, key: &str) -> Option<HashMap<String, String>> {
+        let overrides = self.overrides.read().unwrap();
+
+        let localizations = self
+            .catalogs
+            .keys()
+            .chain(overrides.keys())
+            .filter(|locale| {
+                *locale != DEFAULT_LOCALE && DISCORD_LOCALES.contains(&locale.as_str())
+            })
+            .filter_map(|locale| {
+                self.get(&overrides, locale, key)
+                    .map(|text| (locale.clone(), text.clone()))
+            })
+            .collect::<HashMap<_, _>>();
+
+        if localizations.is_empty() {
+            None
+        } else {
+            Some(localizations)
+        }
+    }
+
+    fn lookup(&self, locale: &str, key: &str) -> Option<String> {
+        let overrides = self.overrides.read().unwrap();
+
+        let language = locale.split_once('-').map(|(language, _)| language);
+
+        [Some(locale), language, Some(DEFAULT_LOCALE)]
+            .into_iter()
+            .flatten()
+            .find_map(|locale| self.get(&overrides, locale, key))
+            .cloned()
+    }
+
+    fn get<'a>(&'a self, overrides: &'a Catalogs, locale: &str, key: &str) -> Option<&'a String> {
+        overrides
+            .get(locale)
+            .and_then(|catalog| catalog.get(key))
+            .or_else(|| {
+                self.catalogs
+                    .get(locale)
+                    .and_then(|catalog| catalog.get(key))
+            })
+    }
+}
+
+/// The locale of the user who triggered the interaction, the guild locale or
+/// [`DEFAULT_LOCALE`] when Discord did not provide it.
+pub fn of(interaction: &Interaction) -> &str {
+    interaction
+        .locale
+        .as_deref()
+        .or(interaction.guild_locale.as_deref())
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// The guild locale, or [`DEFAULT_LOCALE`] outside of guilds. Used for messages everyone in the
+/// guild sees, like forum threads.
+pub fn of_guild(interaction: &Interaction) -> &str {
+    interaction
+        .guild_locale
+        .as_deref()
+        .unwrap_or(DEFAULT_LOCALE)
+}
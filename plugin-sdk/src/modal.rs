@@ -0,0 +1,95 @@
+//! Modals described by a struct, see the [`Modal`](macro@Modal) derive macro for the attributes.
+
+use std::slice;
+
+use twilight_model::{
+    application::interaction::modal::{ModalInteractionComponent, ModalInteractionData},
+    http::interaction::InteractionResponseData,
+    id::{Id, marker::AttachmentMarker},
+};
+
+pub use plugin_sdk_macros::Modal;
+
+use crate::locale::Locales;
+
+/// A modal and the parser of its submissions, usually derived.
+pub trait Modal: Sized {
+    /// The custom ID of the modal, submissions are routed by it.
+    const CUSTOM_ID: &'static str;
+
+    /// The response data which shows the modal in the provided locale, pass it to
+    /// [`crate::interaction::modal`].
+    fn modal(locales: &Locales, locale: &str) -> InteractionResponseData;
+
+    /// Parses a submission of the modal, components are looked up by their custom ID so their
+    /// order does not matter.
+    fn parse(modal_interaction_data: &ModalInteractionData) -> Result<Self, String>;
+}
+
+/// The value of the text input with the provided custom ID.
+pub fn text_input(
+    modal_interaction_data: &ModalInteractionData,
+    custom_id: &str,
+) -> Result<String, String> {
+    match find(&modal_interaction_data.components, custom_id) {
+        Some(ModalInteractionComponent::TextInput(text_input)) => Ok(text_input.value.clone()),
+        _ => Err(missing(modal_interaction_data, "text input", custom_id)),
+    }
+}
+
+/// The selected values of the string select with the provided custom ID.
+pub fn string_select(
+    modal_interaction_data: &ModalInteractionData,
+    custom_id: &str,
+) -> Result<Vec<String>, String> {
+    match find(&modal_interaction_data.components, custom_id) {
+        Some(ModalInteractionComponent::StringSelect(string_select)) => {
+            Ok(string_select.values.clone())
+        }
+        _ => Err(missing(modal_interaction_data, "string select", custom_id)),
+    }
+}
+
+/// The attachment IDs of the file upload with the provided custom ID, the attachments themselves
+/// are in the resolved data.
+pub fn file_upload(
+    modal_interaction_data: &ModalInteractionData,
+    custom_id: &str,
+) -> Result<Vec<Id<AttachmentMarker>>, String> {
+    match find(&modal_interaction_data.components, custom_id) {
+        Some(ModalInteractionComponent::FileUpload(file_upload)) => Ok(file_upload.values.clone()),
+        _ => Err(missing(modal_interaction_data, "file upload", custom_id)),
+    }
+}
+
+fn find<'a>(
+    components: &'a [ModalInteractionComponent],
+    custom_id: &str,
+) -> Option<&'a ModalInteractionComponent> {
+    components.iter().find_map(|component| match component {
+        ModalInteractionComponent::Label(label) => {
+            find(slice::from_ref(label.component.as_ref()), custom_id)
+        }
+        ModalInteractionComponent::TextInput(text_input) if text_input.custom_id == custom_id => {
+            Some(component)
+        }
+        ModalInteractionComponent::StringSelect(string_select)
+            if string_select.custom_id == custom_id =>
+        {
+            Some(component)
+        }
+        ModalInteractionComponent::FileUpload(file_upload)
+            if file_upload.custom_id == custom_id =>
+        {
+            Some(component)
+        }
+        _ => None,
+    })
+}
+
+fn missing(modal_interaction_data: &ModalInteractionData, kind: &str, custom_id: &str) -> String {
+    format!(
+        "The {} modal submission did not contain the {custom_id} {kind}.",
+        modal_interaction_data.custom_id
+    )
+}
@@ -0,0 +1,110 @@
+use crate::discord_bot::plugin::{
+    host_functions::log,
+    host_types::LogLevels,
+    plugin_types::{RegistrationsRequest, RegistrationsRequestDiscordEvents, RegistrationsResult},
+};
+
+/// Discord events with nothing registered, meant to be used with struct update syntax.
+pub fn discord_events() -> RegistrationsRequestDiscordEvents {
+    RegistrationsRequestDiscordEvents {
+        interaction_create: None,
+        guild_member_add: false,
+        guild_member_remove: false,
+        guild_member_update: false,
+        message_create: false,
+        message_delete: false,
+        message_delete_bulk: false,
+        message_reaction_add: false,
+        message_reaction_remove: false,
+        message_update: false,
+        thread_create: false,
+        thread_delete: false,
+        thread_list_sync: false,
+        thread_member_update: false,
+        thread_members_update: false,
+        thread_update: false,
+    }
+}
+
+/// The registrations-request returned by settings-update, only contains the registration kinds
+/// which changed compared to the previous registrations.
+pub fn diff(
+    previous_registrations: Option<RegistrationsRequest>,
+    registrations: RegistrationsRequest,
+) -> RegistrationsRequest {
+    let Some(previous_registrations) = previous_registrations else {
+        return registrations;
+    };
+
+    RegistrationsRequest {
+        discord_events: if previous_registrations.discord_events == registrations.discord_events {
+            None
+        } else {
+            Some(registrations.discord_events.unwrap_or_else(discord_events))
+        },
+        scheduled_jobs: if previous_registrations.scheduled_jobs == registrations.scheduled_jobs {
+            None
+        } else {
+            Some(registrations.scheduled_jobs.unwrap_or_default())
+        },
+        dependency_functions: if previous_registrations.dependency_functions
+            == registrations.dependency_functions
+        {
+            None
+        } else {
+            Some(registrations.dependency_functions.unwrap_or_default())
+        },
+    }
+}
+
+/// Logs the outcome of every registration, rejected registrations are logged as warnings.
+pub fn log_result(registrations_result: RegistrationsResult) {
+    if let Some(interaction_create) = registrations_result
+        .discord_events
+        .and_then(|discord_events| discord_events.interaction_create)
+    {
+        for (name, result) in interaction_create.application_commands {
+            match result {
+                Ok(id) => log(
+                    LogLevels::Debug,
+                    &format!("The {name} command was registered with ID {id}"),
+                ),
+                Err(err) => log(
+                    LogLevels::Warn,
+                    &format!("The {name} command was rejected: {err}"),
+                ),
+            }
+        }
+
+        for (id, result) in interaction_create
+            .message_components
+            .into_iter()
+            .chain(interaction_create.modals)
+        {
+            if let Err(err) = result {
+                log(
+                    LogLevels::Warn,
+                    &format!("The {id} interaction was rejected: {err}"),
+                );
+            }
+        }
+    }
+
+    for (id, result) in registrations_result.scheduled_jobs {
+        if let Err(err) = result {
+            log(
+                LogLevels::Warn,
+                &format!("The {id} scheduled job was rejected: {err}"),
+            );
+        }
+    }
+
+    for (name, result) in registrations_result.dependency_functions {
+        if let Err(err) = result {
+            log(
+                LogLevels::Warn,
+                &format!("The {name} dependency function was rejected: {err}"),
+            );
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Conversion from twilight request builders to the data the `discord-request` host function
+//! expects.
+
+use std::sync::LazyLock;
+
+use serde::de::DeserializeOwned;
+use twilight_http::{Client, request::TryIntoRequest};
+
+use crate::{
+    discord_bot::plugin::{
+        discord_types::{Contents, Requests},
+        host_functions::discord_request,
+    },
+    error::{self, PluginError},
+};
+
+static CLIENT: LazyLock<Client> = LazyLock::new(|| Client::builder().build());
+
+/// A client which is only used to build requests, the host is the one sending them.
+pub fn client() -> &'static Client {
+    &CLIENT
+}
+
+/// The JSON body of the request.
+pub fn body(request: impl TryIntoRequest) -> Result<Vec<u8>, PluginError> {
+    match request.try_into_request() {
+        Ok(request) => Ok(request.body().map(<[u8]>::to_vec).unwrap_or_default()),
+        Err(err) => Err(PluginError::Request(format!(
+            "An error occured while building the Discord request: {err}"
+        ))),
+    }
+}
+
+/// The multipart form of the request if it has one, the JSON body otherwise.
+pub fn contents(request: impl TryIntoRequest) -> Result<Contents, PluginError> {
+    match request.try_into_request() {
+        Ok(request) => match request.form() {
+            Some(form) => Ok(Contents::Form(form.clone().build())),
+            None => Ok(Contents::Json(
+                request.body().map(<[u8]>::to_vec).unwrap_or_default(),
+            )),
+        },
+        Err(err) => Err(PluginError::Request(format!(
+            "An error occured while building the Discord request: {err}"
+        ))),
+    }
+}
+
+/// Sends a request which Discord responds to with JSON and deserializes the response, name is
+/// used in errors, e.g. `created forum thread`.
+pub fn fetch<T: DeserializeOwned>(
+    request: &Requests,
+    name: &'static str,
+) -> Result<T, PluginError> {
+    let Some(response) = discord_request(request)? else {
+        return Err(PluginError::Missing(name));
+    };
+
+    error::deserialize(&response, name)
+}
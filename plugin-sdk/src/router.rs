@@ -0,0 +1,208 @@
+//! Dispatches interactions to their handlers, interactions without a handler get an ephemeral
+//! "unsupported" reply and failed handlers an ephemeral error embed instead of trapping the plugin.
+
+use twilight_model::{
+    application::interaction::{InteractionData, InteractionType},
+    channel::message::MessageFlags,
+    gateway::payload::incoming::InteractionCreate,
+    http::interaction::InteractionResponseData,
+};
+
+use crate::{
+    discord_bot::plugin::{host_functions::log, host_types::LogLevels},
+    error::{self, PluginError},
+    interaction,
+    locale::{self, Locales},
+};
+
+/// Errors are logged and shown to the user, see [`Router::error_embeds`].
+pub type Handler = fn(&InteractionCreate) -> Result<(), PluginError>;
+
+/// How the errors of handlers are shown to the user, they are always logged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorEmbeds {
+    /// The errors are only logged.
+    Hidden,
+    /// Only the user who triggered the interaction sees the error embed.
+    Ephemeral,
+    /// Everyone who can see the channel sees the error embed.
+    Public,
+}
+
+/// Handlers keyed by application command name, for both the commands and their autocompletes,
+/// component custom ID prefix and modal custom ID.
+pub struct Router {
+    commands: Vec<(&'static str, Handler)>,
+    autocompletes: Vec<(&'static str, Handler)>,
+    components: Vec<(&'static str, Handler)>,
+    modals: Vec<(&'static str, Handler)>,
+    error_embeds: fn() -> ErrorEmbeds,
+    locales: &'static Locales,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self {
+            commands: vec![],
+            autocompletes: vec![],
+            components: vec![],
+            modals: vec![],
+            error_embeds: || ErrorEmbeds::Ephemeral,
+            locales: &locale::SDK,
+        }
+    }
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decides how errors are shown to the user, [`ErrorEmbeds::Ephemeral`] by default. Called on
+    /// every error so it can depend on the settings.
+    pub fn error_embeds(mut self, error_embeds: fn() -> ErrorEmbeds) -> Self {
+        self.error_embeds = error_embeds;
+        self
+    }
+
+    /// The locales of the plugin, used for the unsupported replies and the error embeds.
+    pub fn locales(mut self, locales: &'static Locales) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Handles the application command with the provided name.
+    pub fn command(mut self, name: &'static str, handler: Handler) -> Self {
+        self.commands.push((name, handler));
+        self
+    }
+
+    /// Handles the autocomplete interactions of the application command with the provided name,
+    /// the handler responds with [`interaction::autocomplete`].
+    pub fn autocomplete(mut self, name: &'static str, handler: Handler) -> Self {
+        self.autocompletes.push((name, handler));
+        self
+    }
+
+    /// Handles the message components of which the custom ID starts with the provided prefix,
+    /// the first matching prefix wins.
+    pub fn component(mut self, prefix: &'static str, handler: Handler) -> Self {
+        self.components.push((prefix, handler));
+        self
+    }
+
+    /// Handles the submissions of the modal with the provided custom ID.
+    pub fn modal(mut self, custom_id: &'static str, handler: Handler) -> Self {
+        self.modals.push((custom_id, handler));
+        self
+    }
+
+    /// Deserializes the interaction-create event and routes it.
+    pub fn handle(&self, interaction_create: &[u8]) -> Result<(), String> {
+        let interaction_create =
+            error::deserialize::<InteractionCreate>(interaction_create, "interaction")?;
+
+        self.route(&interaction_create)
+    }
+
+    /// Calls the handler registered for the interaction, or replies that it is unsupported. Only
+    /// returns an error when the error of the handler could not be shown to the user.
+    pub fn route(&self, interaction_create: &InteractionCreate) -> Result<(), String> {
+        let handler = match (interaction_create.kind, interaction_create.data.as_ref()) {
+            (
+                InteractionType::ApplicationCommand,
+                Some(InteractionData::ApplicationCommand(command_data)),
+            ) => self
+                .commands
+                .iter()
+                .find(|(name, _)| *name == command_data.name),
+            (
+                InteractionType::ApplicationCommandAutocomplete,
+                Some(InteractionData::ApplicationCommand(command_data)),
+            ) => self
+                .autocompletes
+                .iter()
+                .find(|(name, _)| *name == command_data.name),
+            (
+                InteractionType::MessageComponent,
+                Some(InteractionData::MessageComponent(message_component_data)),
+            ) => self
+                .components
+                .iter()
+                .find(|(prefix, _)| message_component_data.custom_id.starts_with(prefix)),
+            (InteractionType::ModalSubmit, Some(InteractionData::ModalSubmit(modal_data))) => self
+                .modals
+                .iter()
+                .find(|(custom_id, _)| *custom_id == modal_data.custom_id),
+            _ => None,
+        };
+
+        let result = match handler {
+            Some((_, handler)) => handler(interaction_create),
+            None => self.unsupported(interaction_create),
+        };
+
+        let Err(err) = result else {
+            return Ok(());
+        };
+
+        log(
+            LogLevels::Error,
+            &format!(
+                "The {:?} interaction failed, error: {err}",
+                interaction_create.kind
+            ),
+        );
+
+        // Autocomplete interactions can only be responded to with choices
+        if interaction_create.kind == InteractionType::ApplicationCommandAutocomplete {
+            return Ok(());
+        }
+
+        match (self.error_embeds)() {
+            ErrorEmbeds::Hidden => (),
+            ErrorEmbeds::Ephemeral => {
+                interaction::error(self.locales, interaction_create, &err, true)?;
+            }
+            ErrorEmbeds::Public => {
+                interaction::error(self.locales, interaction_create, &err, false)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unsupported(&self, interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        log(
+            LogLevels::Warn,
+            &format!(
+                "Received an unsupported {:?} interaction",
+                interaction_create.kind
+            ),
+        );
+
+        match interaction_create.kind {
+            InteractionType::Ping => Ok(()),
+            InteractionType::ApplicationCommandAutocomplete => {
+                interaction::autocomplete(interaction_create, vec![])?;
+
+                Ok(())
+            }
+            _ => {
+                interaction::reply(
+                    interaction_create,
+                    InteractionResponseData {
+                        content: Some(
+                            self.locales
+                                .text(locale::of(interaction_create), "interaction.unsupported"),
+                        ),
+                        flags: Some(MessageFlags::EPHEMERAL),
+                        ..Default::default()
+                    },
+                )?;
+
+                Ok(())
+            }
+        }
+    }
+}
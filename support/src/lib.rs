@@ -1,76 +1,178 @@
-use std::{
-    sync::{LazyLock, RwLock},
-    time::{Duration, SystemTime, UNIX_EPOCH},
-};
+use std::sync::{LazyLock, RwLock};
 
+use plugin_sdk::{
+    discord_bot::plugin::{
+        discord_types::Requests,
+        plugin_types::{
+            RegistrationsRequest, RegistrationsRequestDiscordEvents,
+            RegistrationsRequestInteractionCreate, RegistrationsResult, SupportedRegistrations,
+        },
+    },
+    embed,
+    error::PluginError,
+    exports::discord_bot::plugin::plugin_functions::{DiscordEvents, Guest},
+    forum, interaction,
+    locale::{self, Catalogs, DEFAULT_LOCALE, Locales},
+    modal::Modal,
+    registrations, request,
+    router::Router,
+};
+use schemars::{JsonSchema, schema_for};
 use serde::Deserialize;
-use twilight_http::{Client as DiscordClient, request::TryIntoRequest};
 use twilight_model::{
     application::{
         command::{Command, CommandType},
-        interaction::{
-            InteractionContextType, InteractionData,
-            modal::{
-                ModalInteractionComponent, ModalInteractionData, ModalInteractionFileUpload,
-                ModalInteractionTextInput,
-            },
-        },
+        interaction::{InteractionContextType, InteractionData, modal::ModalInteractionData},
     },
     channel::{
-        Channel, ChannelType,
-        message::{
-            AllowedMentions, Component, Embed, MentionType, MessageFlags,
-            component::{FileUpload, Label, TextInput, TextInputStyle},
-            embed::EmbedFooter,
-        },
+        Channel,
+        message::{AllowedMentions, MentionType},
     },
     gateway::payload::incoming::InteractionCreate,
     guild::Permissions,
-    http::{
-        attachment::Attachment,
-        interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
+    http::attachment::Attachment,
+    id::{
+        Id,
+        marker::{AttachmentMarker, TagMarker},
     },
-    id::{Id, marker::AttachmentMarker},
     oauth::ApplicationIntegrationType,
-    util::Timestamp,
 };
 use wstd::{
     http::{Client, Request},
     runtime::block_on,
 };
 
-wit_bindgen::generate!({ path: "../wit" });
-
-use crate::{
-    discord_bot::plugin::{
-        discord_types::{Contents, Requests},
-        host_functions::discord_request,
-        plugin_types::{
-            RegistrationsRequest, RegistrationsRequestDiscordEvents,
-            RegistrationsRequestInteractionCreate, SupportedRegistrations,
-        },
-    },
-    exports::discord_bot::plugin::plugin_functions::{DiscordEvents, Guest},
-};
-
 struct Plugin {
     settings: RwLock<PluginSettings>,
+    registrations: RwLock<Option<RegistrationsRequest>>,
 }
 
-#[derive(Deserialize)]
+/// Support questions, bug reports and enhancement requests as forum threads.
+#[derive(Deserialize, JsonSchema)]
 struct PluginSettings {
+    /// The ID of the forum channel support threads are created in.
     channel_id: u64,
+    /// The IDs of the forum tags applied to the support threads.
     tags: PluginSettingsTags,
+    /// Overrides of the localized strings, keyed by locale and then by string key.
+    #[serde(default)]
+    strings: Catalogs,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Copy, Deserialize, JsonSchema)]
 struct PluginSettingsTags {
+    /// The ID of the question forum tag.
     question: u64,
+    /// The ID of the bug forum tag.
     bug: u64,
+    /// The ID of the enhancement forum tag.
     enhancement: u64,
+    /// The ID of the needs triage forum tag.
     needs_triage: u64,
 }
 
+#[derive(Modal)]
+#[modal(id = "support-question", title = "Support Question")]
+struct SupportQuestionModal {
+    #[modal(
+        text_input,
+        label = "Title",
+        description = "Be descriptive.",
+        placeholder = "What... How... Why..."
+    )]
+    title: String,
+    #[modal(
+        text_input,
+        label = "Description",
+        description = "Make sure to provide all relevant information.",
+        placeholder = "Tell us about it!",
+        paragraph
+    )]
+    description: String,
+    #[modal(
+        file_upload,
+        label = "File Upload",
+        description = "Got images or other files? You can share it with us here.",
+        required = false
+    )]
+    files: Vec<Id<AttachmentMarker>>,
+}
+
+#[derive(Modal)]
+#[modal(id = "support-bug", title = "Support Bug")]
+struct SupportBugModal {
+    #[modal(
+        text_input,
+        label = "Name",
+        description = "The name of the extension, website or tool.",
+        placeholder = "e.g. MangaDex"
+    )]
+    name: String,
+    #[modal(
+        text_input,
+        label = "Version",
+        description = "Specify the version you are reporting this bug for.",
+        placeholder = "e.g. v1.0.0-alpha.5"
+    )]
+    version: String,
+    #[modal(
+        text_input,
+        label = "URL",
+        description = "Provide a link to the relevant website.",
+        placeholder = "e.g. https://mangadex.org/"
+    )]
+    url: String,
+    #[modal(
+        text_input,
+        label = "Description",
+        description = "Make sure to provide all relevant information.",
+        placeholder = "Tell us about the bug!",
+        paragraph
+    )]
+    description: String,
+    #[modal(
+        file_upload,
+        label = "File Upload",
+        description = "Got images or other files? You can share it with us here.",
+        required = false
+    )]
+    files: Vec<Id<AttachmentMarker>>,
+}
+
+#[derive(Modal)]
+#[modal(id = "support-enhancement", title = "Support Enhancement")]
+struct SupportEnhancementModal {
+    #[modal(
+        text_input,
+        label = "Name",
+        description = "The name of the extension, website, or tool.",
+        placeholder = "e.g. MangaDex"
+    )]
+    name: String,
+    #[modal(
+        text_input,
+        label = "URL",
+        description = "Provide a link to the relevant website.",
+        placeholder = "e.g. https://mangadex.org/"
+    )]
+    url: String,
+    #[modal(
+        text_input,
+        label = "Description",
+        description = "Describe the improvement or feature you would like to see and what benefits it will bring!",
+        placeholder = "What should be improved or added? Why would it be useful?",
+        paragraph
+    )]
+    description: String,
+    #[modal(
+        file_upload,
+        label = "File Upload",
+        description = "Got images or other files? You can share it with us here.",
+        required = false
+    )]
+    files: Vec<Id<AttachmentMarker>>,
+}
+
 static CONTEXT: LazyLock<Plugin> = LazyLock::new(|| Plugin {
     settings: RwLock::new(PluginSettings {
         channel_id: 0,
@@ -80,7 +182,28 @@ static CONTEXT: LazyLock<Plugin> = LazyLock::new(|| Plugin {
             enhancement: 0,
             needs_triage: 0,
         },
+        strings: Catalogs::new(),
     }),
+    registrations: RwLock::new(None),
+});
+
+static LOCALES: LazyLock<Locales> = LazyLock::new(|| plugin_sdk::locales!("en-US", "nl"));
+
+static ROUTER: LazyLock<Router> = LazyLock::new(|| {
+    Router::new()
+        .locales(&LOCALES)
+        .command("support-question", Plugin::get_support_question)
+        .command("support-bug", Plugin::get_support_bug)
+        .command("support-enhancement", Plugin::get_support_enhancement)
+        .component("get-support-question", Plugin::get_support_question)
+        .component("get-support-bug", Plugin::get_support_bug)
+        .component("get-support-enhancement", Plugin::get_support_enhancement)
+        .modal(SupportQuestionModal::CUSTOM_ID, Plugin::support_question)
+        .modal(SupportBugModal::CUSTOM_ID, Plugin::support_bug)
+        .modal(
+            SupportEnhancementModal::CUSTOM_ID,
+            Plugin::support_enhancement,
+        )
 });
 
 impl Guest for Plugin {
@@ -96,87 +219,95 @@ impl Guest for Plugin {
             ));
         }
 
-        let settings = match sonic_rs::from_slice::<PluginSettings>(&settings) {
-            Ok(settings) => settings,
-            Err(err) => {
-                return Err(format!(
-                    "The provided settings were of the incorrect structure, error: {}",
-                    &err,
-                ));
+        let channel = Plugin::update_settings(&settings)?;
+
+        let registrations = Plugin::registrations(&channel);
+
+        *CONTEXT.registrations.write().unwrap() = Some(registrations.clone());
+
+        Ok(registrations)
+    }
+
+    fn settings_update(settings: Vec<u8>) -> Result<RegistrationsRequest, String> {
+        let channel = Plugin::update_settings(&settings)?;
+
+        let registrations = Plugin::registrations(&channel);
+
+        let previous_registrations = CONTEXT
+            .registrations
+            .write()
+            .unwrap()
+            .replace(registrations.clone());
+
+        Ok(registrations::diff(previous_registrations, registrations))
+    }
+
+    fn settings_schema() -> Vec<u8> {
+        sonic_rs::to_vec(&schema_for!(PluginSettings)).unwrap()
+    }
+
+    fn registrations_processed(registrations_result: RegistrationsResult) -> Result<(), String> {
+        registrations::log_result(registrations_result);
+
+        Ok(())
+    }
+
+    fn shutdown() -> Result<(), String> {
+        Ok(())
+    }
+
+    fn discord_event(event: DiscordEvents) -> Result<(), String> {
+        match event {
+            DiscordEvents::InteractionCreate(interaction_create) => {
+                ROUTER.handle(&interaction_create)
             }
-        };
+            _ => Err(String::from(
+                "The plugin received an event it did not register.",
+            )),
+        }
+    }
 
-        let get_channel_response =
-            discord_request(&Requests::GetChannel(settings.channel_id))?.unwrap();
+    fn scheduled_job(job: String, _payload: Option<Vec<u8>>) -> Result<(), String> {
+        Err(format!("Unknown scheduled job: {job}"))
+    }
+
+    fn dependency_function(function: String, _params: Vec<u8>) -> Result<Vec<u8>, String> {
+        Err(format!(
+            "The {function} dependency function does not exist."
+        ))
+    }
+}
 
-        let channel = match sonic_rs::from_slice::<Channel>(&get_channel_response) {
-            Ok(channel) => channel,
+impl Plugin {
+    fn update_settings(settings: &[u8]) -> Result<Channel, String> {
+        let settings = match sonic_rs::from_slice::<PluginSettings>(settings) {
+            Ok(settings) => settings,
             Err(err) => {
                 return Err(format!(
-                    "Something went wrong while deserializing the response from Discord, error {}",
-                    &err
+                    "The provided settings were of the incorrect structure, error: {}",
+                    &err,
                 ));
             }
         };
 
-        if channel.kind != ChannelType::GuildForum {
-            return Err(String::from(
-                "The provided support channel needs to be of the forum type.",
-            ));
-        }
-
-        CONTEXT.settings.write().unwrap().channel_id = settings.channel_id;
-
-        if !channel
-            .available_tags
-            .as_ref()
-            .unwrap()
-            .iter()
-            .any(|forum_tag| forum_tag.id == settings.tags.question)
-        {
-            return Err(String::from(
-                "The provided question tag ID was not available in the provided forum channel.",
-            ));
-        }
-
-        if !channel
-            .available_tags
-            .as_ref()
-            .unwrap()
-            .iter()
-            .any(|forum_tag| forum_tag.id == settings.tags.bug)
-        {
-            return Err(String::from(
-                "The provided bug tag ID was not available in the provided forum channel.",
-            ));
-        }
+        let channel = forum::get_channel(
+            settings.channel_id,
+            &[
+                ("question", settings.tags.question),
+                ("bug", settings.tags.bug),
+                ("enhancement", settings.tags.enhancement),
+                ("needs triage", settings.tags.needs_triage),
+            ],
+        )?;
 
-        if !channel
-            .available_tags
-            .as_ref()
-            .unwrap()
-            .iter()
-            .any(|forum_tag| forum_tag.id == settings.tags.enhancement)
-        {
-            return Err(String::from(
-                "The provided enhancement tag ID was not available in the provided forum channel.",
-            ));
-        }
+        LOCALES.set_overrides(settings.strings.clone());
 
-        if !channel
-            .available_tags
-            .as_ref()
-            .unwrap()
-            .iter()
-            .any(|forum_tag| forum_tag.id == settings.tags.needs_triage)
-        {
-            return Err(String::from(
-                "The provided needs trage tag ID was not available in the provided forum channel.",
-            ));
-        }
+        *CONTEXT.settings.write().unwrap() = settings;
 
-        CONTEXT.settings.write().unwrap().tags = settings.tags;
+        Ok(channel)
+    }
 
+    fn registrations(channel: &Channel) -> RegistrationsRequest {
         let commands = vec![
             sonic_rs::to_vec(&Command {
                 application_id: None,
@@ -184,14 +315,15 @@ impl Guest for Plugin {
                 default_member_permissions: Some(Permissions::SEND_MESSAGES),
                 #[allow(deprecated)]
                 dm_permission: None,
-                description: String::from("Ask questions not related to bugs or enhancements."),
-                description_localizations: None,
+                description: LOCALES.text(DEFAULT_LOCALE, "command.support-question.description"),
+                description_localizations: LOCALES
+                    .localizations("command.support-question.description"),
                 guild_id: Some(channel.guild_id.unwrap()),
                 id: None,
                 integration_types: Some(vec![ApplicationIntegrationType::GuildInstall]),
                 kind: CommandType::ChatInput,
                 name: String::from("support-question"),
-                name_localizations: None,
+                name_localizations: LOCALES.localizations("command.support-question.name"),
                 nsfw: Some(false),
                 options: vec![],
                 version: Id::new(1),
@@ -203,14 +335,14 @@ impl Guest for Plugin {
                 default_member_permissions: Some(Permissions::SEND_MESSAGES),
                 #[allow(deprecated)]
                 dm_permission: None,
-                description: String::from("Report bugs with extensions, websites or tooling."),
-                description_localizations: None,
+                description: LOCALES.text(DEFAULT_LOCALE, "command.support-bug.description"),
+                description_localizations: LOCALES.localizations("command.support-bug.description"),
                 guild_id: Some(channel.guild_id.unwrap()),
                 id: None,
                 integration_types: Some(vec![ApplicationIntegrationType::GuildInstall]),
                 kind: CommandType::ChatInput,
                 name: String::from("support-bug"),
-                name_localizations: None,
+                name_localizations: LOCALES.localizations("command.support-bug.name"),
                 nsfw: Some(false),
                 options: vec![],
                 version: Id::new(1),
@@ -222,16 +354,16 @@ impl Guest for Plugin {
                 default_member_permissions: Some(Permissions::SEND_MESSAGES),
                 #[allow(deprecated)]
                 dm_permission: None,
-                description: String::from(
-                    "Request an enhancement to our extensions, website or tooling.",
-                ),
-                description_localizations: None,
+                description: LOCALES
+                    .text(DEFAULT_LOCALE, "command.support-enhancement.description"),
+                description_localizations: LOCALES
+                    .localizations("command.support-enhancement.description"),
                 guild_id: Some(channel.guild_id.unwrap()),
                 id: None,
                 integration_types: Some(vec![ApplicationIntegrationType::GuildInstall]),
                 kind: CommandType::ChatInput,
                 name: String::from("support-enhancement"),
-                name_localizations: None,
+                name_localizations: LOCALES.localizations("command.support-enhancement.name"),
                 nsfw: Some(false),
                 options: vec![],
                 version: Id::new(1),
@@ -239,7 +371,7 @@ impl Guest for Plugin {
             .unwrap(),
         ];
 
-        Ok(RegistrationsRequest {
+        RegistrationsRequest {
             discord_events: Some(RegistrationsRequestDiscordEvents {
                 interaction_create: Some(RegistrationsRequestInteractionCreate {
                     application_commands: Some(commands),
@@ -249,651 +381,230 @@ impl Guest for Plugin {
                         String::from("get-support-enhancement"),
                     ]),
                     modals: Some(vec![
-                        String::from("support-question"),
-                        String::from("support-bug"),
-                        String::from("support-enhancement"),
+                        String::from(SupportQuestionModal::CUSTOM_ID),
+                        String::from(SupportBugModal::CUSTOM_ID),
+                        String::from(SupportEnhancementModal::CUSTOM_ID),
                     ]),
                 }),
-                message_create: false,
-                thread_create: false,
-                thread_delete: false,
-                thread_list_sync: false,
-                thread_member_update: false,
-                thread_members_update: false,
-                thread_update: false,
+                ..registrations::discord_events()
             }),
             scheduled_jobs: None,
             dependency_functions: None,
-        })
-    }
-
-    fn shutdown() -> Result<(), String> {
-        Ok(())
-    }
-
-    fn discord_event(event: DiscordEvents) -> Result<(), String> {
-        match event {
-            DiscordEvents::InteractionCreate(interaction_create) => {
-                let interaction_create =
-                    sonic_rs::from_slice::<InteractionCreate>(&interaction_create).unwrap();
-
-                match interaction_create.data.as_ref() {
-                    Some(InteractionData::ApplicationCommand(command_data)) => {
-                        match command_data.name.as_str() {
-                            "support-question" => Plugin::get_support_question(&interaction_create),
-                            "support-bug" => Plugin::get_support_bug(&interaction_create),
-                            "support-enhancement" => {
-                                Plugin::get_support_enhancement(&interaction_create)
-                            }
-                            &_ => unimplemented!(),
-                        }
-                    }
-                    Some(InteractionData::ModalSubmit(modal_interaction_data)) => {
-                        match modal_interaction_data.custom_id.as_str() {
-                            "support-question" => Plugin::support_question(&interaction_create),
-                            "support-bug" => Plugin::support_bug(&interaction_create),
-                            "support-enhancement" => {
-                                Plugin::support_enhancement(&interaction_create)
-                            }
-                            &_ => unimplemented!(),
-                        }
-                    }
-                    Some(InteractionData::MessageComponent(message_component_interaction_data)) => {
-                        match message_component_interaction_data.custom_id.as_str() {
-                            "get-support-question" => {
-                                Plugin::get_support_question(&interaction_create)
-                            }
-                            "get-support-bug" => Plugin::get_support_bug(&interaction_create),
-                            "get-support-enhancement" => {
-                                Plugin::get_support_enhancement(&interaction_create)
-                            }
-                            &_ => unimplemented!(),
-                        }
-                    }
-                    _ => unimplemented!(),
-                }
-            }
-            _ => unimplemented!(),
         }
     }
 
-    fn scheduled_job(_job: String) -> Result<(), String> {
-        unimplemented!();
-    }
+    fn get_support_question(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        interaction::modal(
+            interaction_create,
+            SupportQuestionModal::modal(&LOCALES, locale::of(interaction_create)),
+        )?;
 
-    fn dependency_function(_function: String, _params: Vec<u8>) -> Result<Vec<u8>, String> {
-        unimplemented!();
+        Ok(())
     }
-}
-
-impl Plugin {
-    fn get_support_question(interaction_create: &InteractionCreate) -> Result<(), String> {
-        let modal = InteractionResponse {
-            kind: InteractionResponseType::Modal,
-            data: Some(InteractionResponseData {
-                allowed_mentions: Some(AllowedMentions {
-                    parse: vec![MentionType::Users],
-                    replied_user: true,
-                    roles: vec![],
-                    users: vec![],
-                }),
-                attachments: None,
-                choices: None,
-                components: Some(vec![
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Title"),
-                        description: Some(String::from("Be descriptive.")),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("title"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("What... How... Why...")),
-                            required: Some(true),
-                            style: TextInputStyle::Short,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Description"),
-                        description: Some(String::from(
-                            "Make sure to provide all relevant information.",
-                        )),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("description"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("Tell us about it!")),
-                            required: Some(true),
-                            style: TextInputStyle::Paragraph,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("File Upload"),
-                        description: Some(String::from(
-                            "Got images or other files? You can share it with us here.",
-                        )),
-                        component: Box::new(Component::FileUpload(FileUpload {
-                            id: None,
-                            custom_id: String::from("files"),
-                            max_values: None,
-                            min_values: None,
-                            required: Some(false),
-                        })),
-                    }),
-                ]),
-                content: None,
-                custom_id: Some(String::from("support-question")),
-                embeds: None,
-                flags: None,
-                title: Some(String::from("Support Question")),
-                tts: None,
-                poll: None,
-            }),
-        };
 
-        discord_request(
-            &discord_bot::plugin::discord_types::Requests::InteractionCallback((
-                interaction_create.id.get(),
-                interaction_create.token.clone(),
-                true,
-                sonic_rs::to_vec(&modal).unwrap(),
-            )),
+    fn get_support_bug(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        interaction::modal(
+            interaction_create,
+            SupportBugModal::modal(&LOCALES, locale::of(interaction_create)),
         )?;
 
         Ok(())
     }
 
-    fn get_support_bug(interaction_create: &InteractionCreate) -> Result<(), String> {
-        let modal = InteractionResponse {
-            kind: InteractionResponseType::Modal,
-            data: Some(InteractionResponseData {
-                allowed_mentions: Some(AllowedMentions {
-                    parse: vec![MentionType::Users],
-                    replied_user: true,
-                    roles: vec![],
-                    users: vec![],
-                }),
-                attachments: None,
-                choices: None,
-                components: Some(vec![
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Name"),
-                        description: Some(String::from(
-                            "The name of the extension, website or tool.",
-                        )),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("name"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("e.g. MangaDex")),
-                            required: Some(true),
-                            style: TextInputStyle::Short,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Version"),
-                        description: Some(String::from(
-                            "Specify the version you are reporting this bug for.",
-                        )),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("version"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("e.g. v1.0.0-alpha.5")),
-                            required: Some(true),
-                            style: TextInputStyle::Short,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("URL"),
-                        description: Some(String::from("Provide a link to the relevant website.")),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("url"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("e.g. https://mangadex.org/")),
-                            required: Some(true),
-                            style: TextInputStyle::Short,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Description"),
-                        description: Some(String::from(
-                            "Make sure to provide all relevant information.",
-                        )),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("description"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("Tell us about the bug!")),
-                            required: Some(true),
-                            style: TextInputStyle::Paragraph,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("File Upload"),
-                        description: Some(String::from(
-                            "Got images or other files? You can share it with us here.",
-                        )),
-                        component: Box::new(Component::FileUpload(FileUpload {
-                            id: None,
-                            custom_id: String::from("files"),
-                            max_values: None,
-                            min_values: None,
-                            required: Some(false),
-                        })),
-                    }),
-                ]),
-                content: None,
-                custom_id: Some(String::from("support-bug")),
-                embeds: None,
-                flags: None,
-                title: Some(String::from("Support Bug")),
-                tts: None,
-                poll: None,
-            }),
-        };
-
-        discord_request(
-            &discord_bot::plugin::discord_types::Requests::InteractionCallback((
-                interaction_create.id.get(),
-                interaction_create.token.clone(),
-                true,
-                sonic_rs::to_vec(&modal).unwrap(),
-            )),
+    fn get_support_enhancement(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        interaction::modal(
+            interaction_create,
+            SupportEnhancementModal::modal(&LOCALES, locale::of(interaction_create)),
         )?;
 
         Ok(())
     }
 
-    fn get_support_enhancement(interaction_create: &InteractionCreate) -> Result<(), String> {
-        let modal = InteractionResponse {
-            kind: InteractionResponseType::Modal,
-            data: Some(InteractionResponseData {
-                allowed_mentions: Some(AllowedMentions {
-                    parse: vec![MentionType::Users],
-                    replied_user: true,
-                    roles: vec![],
-                    users: vec![],
-                }),
-                attachments: None,
-                choices: None,
-                components: Some(vec![
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Name"),
-                        description: Some(String::from(
-                            "The name of the extension, website, or tool.",
-                        )),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("name"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("e.g. MangaDex")),
-                            required: Some(true),
-                            style: TextInputStyle::Short,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("URL"),
-                        description: Some(String::from("Provide a link to the relevant website.")),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("url"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from("e.g. https://mangadex.org/")),
-                            required: Some(true),
-                            style: TextInputStyle::Short,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("Description"),
-                        description: Some(String::from(
-                            "Describe the improvement or feature you would like to see and what benefits it will bring!",
-                        )),
-                        component: Box::new(Component::TextInput(TextInput {
-                            id: None,
-                            custom_id: String::from("description"),
-                            max_length: None,
-                            min_length: None,
-                            placeholder: Some(String::from(
-                                "What should be improved or added? Why would it be useful?",
-                            )),
-                            required: Some(true),
-                            style: TextInputStyle::Paragraph,
-                            value: None,
-                            #[allow(deprecated)]
-                            label: None,
-                        })),
-                    }),
-                    Component::Label(Label {
-                        id: None,
-                        label: String::from("File Upload"),
-                        description: Some(String::from(
-                            "Got images or other files? You can share it with us here.",
-                        )),
-                        component: Box::new(Component::FileUpload(FileUpload {
-                            id: None,
-                            custom_id: String::from("files"),
-                            max_values: None,
-                            min_values: None,
-                            required: Some(false),
-                        })),
-                    }),
-                ]),
-                content: None,
-                custom_id: Some(String::from("support-enhancement")),
-                embeds: None,
-                flags: None,
-                title: Some(String::from("Support Enhancement")),
-                tts: None,
-                poll: None,
-            }),
+    fn support_question(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Some(InteractionData::ModalSubmit(modal_interaction_data)) =
+            interaction_create.data.as_ref()
+        else {
+            return Err(PluginError::Missing("modal submission data"));
         };
 
-        discord_request(
-            &discord_bot::plugin::discord_types::Requests::InteractionCallback((
-                interaction_create.id.get(),
-                interaction_create.token.clone(),
-                true,
-                sonic_rs::to_vec(&modal).unwrap(),
-            )),
-        )?;
+        let modal = SupportQuestionModal::parse(modal_interaction_data)?;
 
-        Ok(())
-    }
+        interaction::defer_ephemeral(interaction_create)?;
 
-    fn support_question(interaction_create: &InteractionCreate) -> Result<(), String> {
-        let (modal_interaction_data, title, description, files) =
-            Self::parse_support_question_modal(interaction_create);
-
-        discord_request(&Requests::InteractionCallback((
-            interaction_create.id.get(),
-            interaction_create.token.clone(),
-            true,
-            sonic_rs::to_vec(&InteractionResponse {
-                kind: InteractionResponseType::DeferredChannelMessageWithSource,
-                data: Some(InteractionResponseData {
-                    allowed_mentions: None,
-                    attachments: None,
-                    choices: None,
-                    components: None,
-                    content: None,
-                    custom_id: None,
-                    embeds: None,
-                    flags: Some(MessageFlags::EPHEMERAL),
-                    title: None,
-                    tts: None,
-                    poll: None,
-                }),
-            })
-            .unwrap(),
-        )))?;
+        let tags = CONTEXT.settings.read().unwrap().tags;
 
-        let support_question_thread = Self::create_support_question_forum_thread(
+        let thread = Self::create_forum_thread(
             interaction_create,
             modal_interaction_data,
-            &title.value,
-            &description.value,
-            &files.values,
+            &modal.title,
+            &[tags.question],
+            &modal.description,
+            &modal.files,
         )?;
 
-        let mut embed = Self::base_embed(interaction_create);
+        Self::thread_created(
+            interaction_create,
+            "embed.support-question-created",
+            &thread,
+        )
+    }
 
-        embed.title = Some(String::from("Created Support Question"));
+    fn support_bug(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Some(InteractionData::ModalSubmit(modal_interaction_data)) =
+            interaction_create.data.as_ref()
+        else {
+            return Err(PluginError::Missing("modal submission data"));
+        };
 
-        embed.description = Some(format!(
-            "A support question has been created: <#{}>",
-            support_question_thread.id
-        ));
+        let modal = SupportBugModal::parse(modal_interaction_data)?;
 
-        let client = DiscordClient::builder().build();
-
-        let request = match client
-            .create_message(interaction_create.channel.as_ref().unwrap().id)
-            .allowed_mentions(Some(&AllowedMentions {
-                parse: vec![MentionType::Users],
-                replied_user: false,
-                roles: vec![],
-                users: vec![],
-            }))
-            .embeds(&[embed])
-            .flags(MessageFlags::EPHEMERAL)
-            .try_into_request()
-        {
-            Ok(request) => request,
-            Err(err) => {
-                return Err(format!("{err}"));
-            }
-        };
+        interaction::defer_ephemeral(interaction_create)?;
 
-        discord_request(&Requests::UpdateInteractionOriginal((
-            interaction_create.application_id.get(),
-            interaction_create.token.clone(),
-            request.body().unwrap().to_vec(),
-        )))?;
+        let locale = locale::of_guild(interaction_create);
 
-        Ok(())
-    }
+        let tags = CONTEXT.settings.read().unwrap().tags;
 
-    fn support_bug(_interaction_create: &InteractionCreate) -> Result<(), String> {
-        todo!()
-    }
+        let thread = Self::create_forum_thread(
+            interaction_create,
+            modal_interaction_data,
+            &LOCALES.format(
+                locale,
+                "thread.support-bug.title",
+                &[("name", &modal.name), ("version", &modal.version)],
+            ),
+            &[tags.bug, tags.needs_triage],
+            &LOCALES.format(
+                locale,
+                "thread.support-bug.description",
+                &[
+                    ("name", &modal.name),
+                    ("version", &modal.version),
+                    ("url", &modal.url),
+                    ("description", &modal.description),
+                ],
+            ),
+            &modal.files,
+        )?;
 
-    fn support_enhancement(_interaction_create: &InteractionCreate) -> Result<(), String> {
-        todo!()
+        Self::thread_created(interaction_create, "embed.support-bug-created", &thread)
     }
 
-    fn parse_support_question_modal(
-        interaction_create: &InteractionCreate,
-    ) -> (
-        &ModalInteractionData,
-        &ModalInteractionTextInput,
-        &ModalInteractionTextInput,
-        &ModalInteractionFileUpload,
-    ) {
-        let InteractionData::ModalSubmit(modal_interaction_data) =
-            interaction_create.data.as_ref().unwrap()
+    fn support_enhancement(interaction_create: &InteractionCreate) -> Result<(), PluginError> {
+        let Some(InteractionData::ModalSubmit(modal_interaction_data)) =
+            interaction_create.data.as_ref()
         else {
-            unreachable!()
+            return Err(PluginError::Missing("modal submission data"));
         };
 
-        let ModalInteractionComponent::TextInput(support_question_title) = ({
-            let ModalInteractionComponent::Label(support_question_title_label) =
-                &modal_interaction_data.components[0]
-            else {
-                unreachable!()
-            };
+        let modal = SupportEnhancementModal::parse(modal_interaction_data)?;
 
-            support_question_title_label.component.as_ref()
-        }) else {
-            unreachable!()
-        };
+        interaction::defer_ephemeral(interaction_create)?;
 
-        let ModalInteractionComponent::TextInput(support_question_description) = ({
-            let ModalInteractionComponent::Label(support_question_description_label) =
-                &modal_interaction_data.components[1]
-            else {
-                unreachable!()
-            };
-
-            support_question_description_label.component.as_ref()
-        }) else {
-            unreachable!()
-        };
-
-        let ModalInteractionComponent::FileUpload(support_question_files) = ({
-            let ModalInteractionComponent::Label(support_question_files_label) =
-                &modal_interaction_data.components[2]
-            else {
-                unreachable!()
-            };
+        let locale = locale::of_guild(interaction_create);
 
-            support_question_files_label.component.as_ref()
-        }) else {
-            unreachable!()
-        };
+        let tags = CONTEXT.settings.read().unwrap().tags;
 
-        (
+        let thread = Self::create_forum_thread(
+            interaction_create,
             modal_interaction_data,
-            support_question_title,
-            support_question_description,
-            support_question_files,
+            &LOCALES.format(
+                locale,
+                "thread.support-enhancement.title",
+                &[("name", &modal.name)],
+            ),
+            &[tags.enhancement, tags.needs_triage],
+            &LOCALES.format(
+                locale,
+                "thread.support-enhancement.description",
+                &[
+                    ("name", &modal.name),
+                    ("url", &modal.url),
+                    ("description", &modal.description),
+                ],
+            ),
+            &modal.files,
+        )?;
+
+        Self::thread_created(
+            interaction_create,
+            "embed.support-enhancement-created",
+            &thread,
         )
     }
 
-    fn base_embed(interaction_create: &InteractionCreate) -> Embed {
-        Embed {
-            author: None,
-            color: Some(0x00E7_2323),
-            description: None,
-            fields: vec![],
-            footer: Some(EmbedFooter {
-                icon_url: interaction_create
-                    .member
-                    .as_ref()
-                    .unwrap()
-                    .user
-                    .as_ref()
-                    .unwrap()
-                    .avatar
-                    .map(|avatar| {
-                        format!(
-                            "https://cdn.discordapp.com/avatars/{}/{}.webp",
-                            interaction_create
-                                .member
-                                .as_ref()
-                                .unwrap()
-                                .user
-                                .as_ref()
-                                .unwrap()
-                                .id
-                                .get(),
-                            avatar
-                        )
-                    }),
-                proxy_icon_url: None,
-                text: format!(
-                    "Requested by {}",
-                    interaction_create
-                        .member
-                        .as_ref()
-                        .unwrap()
-                        .user
-                        .as_ref()
-                        .unwrap()
-                        .name
-                        .clone()
-                ),
-            }),
-            image: None,
-            kind: String::from("rich"),
-            provider: None,
-            thumbnail: None,
-            timestamp: Some(
-                Timestamp::from_secs(
-                    SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap_or(Duration::new(0, 0))
-                        .as_secs()
-                        .try_into()
-                        .unwrap_or(0),
-                )
-                .unwrap_or(Timestamp::from_secs(0).unwrap()),
-            ),
-            title: None,
-            url: None,
-            video: None,
-        }
+    /// Replaces the deferred response with an embed linking the created thread, key is the prefix
+    /// of the embed title and description strings.
+    fn thread_created(
+        interaction_create: &InteractionCreate,
+        key: &str,
+        thread: &Channel,
+    ) -> Result<(), PluginError> {
+        let locale = locale::of(interaction_create);
+
+        let mut embed = embed::requested_by(&LOCALES, interaction_create);
+
+        embed.title = Some(LOCALES.text(locale, &format!("{key}.title")));
+
+        embed.description = Some(LOCALES.format(
+            locale,
+            &format!("{key}.description"),
+            &[("thread", &format!("<#{}>", thread.id))],
+        ));
+
+        interaction::update_original_embeds(interaction_create, &[embed])?;
+
+        Ok(())
     }
 
-    fn create_support_question_forum_thread(
+    fn create_forum_thread(
         interaction_create: &InteractionCreate,
         modal_interaction_data: &ModalInteractionData,
         title: &str,
+        tags: &[u64],
         description: &str,
         file_ids: &[Id<AttachmentMarker>],
-    ) -> Result<Channel, String> {
-        let mut attachments = vec![];
+    ) -> Result<Channel, PluginError> {
+        let Some(author) = interaction_create.author() else {
+            return Err(PluginError::Missing("interaction author"));
+        };
 
-        let client = DiscordClient::builder().build();
+        let mut attachments = vec![];
 
         for file_id in file_ids {
-            let file = modal_interaction_data
+            let Some(file) = modal_interaction_data
                 .resolved
                 .as_ref()
-                .unwrap()
-                .attachments
-                .get(file_id)
-                .unwrap();
+                .and_then(|resolved| resolved.attachments.get(file_id))
+            else {
+                return Err(PluginError::Missing("uploaded file"));
+            };
 
             let http_client = Client::new();
 
             let request = match Request::get(&file.url).body(()) {
                 Ok(request) => request,
                 Err(err) => {
-                    return Err(format!(
+                    return Err(PluginError::Other(format!(
                         "An error occured while building the request to fetch an uploaded file: {err}"
-                    ));
+                    )));
                 }
             };
 
             let mut response = match block_on(async { http_client.send(request).await }) {
                 Ok(response) => response,
                 Err(err) => {
-                    return Err(format!(
+                    return Err(PluginError::Other(format!(
                         "An error occured while fetching an uploaded file: {err}"
-                    ));
+                    )));
                 }
             };
 
             let file_bytes = match block_on(response.body_mut().contents()) {
                 Ok(file) => file.to_vec(),
                 Err(err) => {
-                    return Err(format!(
+                    return Err(PluginError::Other(format!(
                         "An error occured while reading the contents of the response body: {err}"
-                    ));
+                    )));
                 }
             };
 
@@ -905,57 +616,39 @@ impl Plugin {
             });
         }
 
-        let request = match client
-            .create_forum_thread(Id::new(CONTEXT.settings.read().unwrap().channel_id), title)
-            .applied_tags(&[Id::new(CONTEXT.settings.read().unwrap().tags.question)])
-            .message()
-            .allowed_mentions(Some(&AllowedMentions {
-                parse: vec![MentionType::Users],
-                replied_user: false,
-                roles: Vec::new(),
-                users: Vec::new(),
-            }))
-            .content(&format!(
-                "{}\n\n**Posted by:** <@{}>",
-                description,
-                interaction_create
-                    .member
-                    .as_ref()
-                    .unwrap()
-                    .user
-                    .as_ref()
-                    .unwrap()
-                    .id
-                    .get()
-            ))
-            .attachments(&attachments)
-            .try_into_request()
-        {
-            Ok(request) => request,
-            Err(err) => {
-                return Err(format!(
-                    "An error occured while building the Discord request: {err}"
-                ));
-            }
-        };
-
-        let content = if file_ids.is_empty() {
-            Contents::Form(request.body().unwrap().to_owned())
-        } else {
-            Contents::Form(request.form().unwrap().to_owned().build())
-        };
-
-        let create_forum_thread_response = discord_request(&Requests::CreateForumThread((
-            CONTEXT.settings.read().unwrap().channel_id,
-            content,
-        )))?
-        .unwrap();
+        let content = request::contents(
+            request::client()
+                .create_forum_thread(Id::new(CONTEXT.settings.read().unwrap().channel_id), title)
+                .applied_tags(
+                    &tags
+                        .iter()
+                        .copied()
+                        .map(Id::new)
+                        .collect::<Vec<Id<TagMarker>>>(),
+                )
+                .message()
+                .allowed_mentions(Some(&AllowedMentions {
+                    parse: vec![MentionType::Users],
+                    replied_user: false,
+                    roles: Vec::new(),
+                    users: Vec::new(),
+                }))
+                .content(&LOCALES.format(
+                    locale::of_guild(interaction_create),
+                    "thread.posted-by",
+                    &[
+                        ("description", &description),
+                        ("user", &format!("<@{}>", author.id.get())),
+                    ],
+                ))
+                .attachments(&attachments),
+        )?;
 
-        match sonic_rs::from_slice::<Channel>(&create_forum_thread_response) {
-            Ok(support_question_thread) => Ok(support_question_thread),
-            Err(err) => Err(err.to_string()),
-        }
+        request::fetch(
+            &Requests::CreateForumThread((CONTEXT.settings.read().unwrap().channel_id, content)),
+            "created forum thread",
+        )
     }
 }
 
-export!(Plugin);
+plugin_sdk::export!(Plugin);